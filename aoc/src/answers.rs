@@ -0,0 +1,133 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days::{self, Part};
+
+// The expected answer for one part of a day on one of its input files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: Part,
+    // file name within the day's directory, e.g. "input_test2.txt"
+    pub input: String,
+    // None if the part doesn't apply to this input, e.g. an example that
+    // only exists for part 2.
+    pub answer: Option<String>,
+}
+
+// The checked-in registry of expected answers, one per line as
+//
+//   <day> <part> <input> <answer>
+//
+// Blank lines and lines starting with '#' are ignored, and an answer of "-"
+// means the part doesn't apply to that input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub entries: Vec<Expected>,
+}
+
+pub fn default_path() -> PathBuf {
+    days::workspace_root().join("answers.txt")
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let text = fs::read_to_string(path).
+            map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+
+        Answers::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let mut answers = Answers::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, part, input, answer] = fields[..] else {
+                return Err(format!("line {}: expected <day> <part> <input> <answer>", i + 1));
+            };
+
+            let day = day.parse::<u32>().map_err(|_| format!("line {}: invalid day '{}'", i + 1, day))?;
+            let part = part.parse::<Part>().map_err(|e| format!("line {}: {}", i + 1, e))?;
+
+            if answers.get(day, part, input).is_some() {
+                return Err(format!("line {}: duplicate answer for day {} part {} on {}", i + 1, day, part, input));
+            }
+
+            answers.entries.push(Expected {
+                day,
+                part,
+                input: input.to_string(),
+                answer: if answer == "-" { None } else { Some(answer.to_string()) },
+            });
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: Part, input: &str) -> Option<&Expected> {
+        self.entries.iter().find(|e| e.day == day && e.part == part && e.input == input)
+    }
+}
+
+impl Expected {
+    // The line for this answer in the registry, lined up with the others.
+    pub fn to_line(&self) -> String {
+        format!("{:<5} {:<4} {:<16} {}", self.day, self.part, self.input, self.answer.as_deref().unwrap_or("-"))
+    }
+}
+
+// Add answers to the registry text, each after the last line for its day
+// (or in a new group at the end), leaving everything else as it was.
+pub fn insert(text: &str, new: &[Expected]) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+    for expected in new {
+        let day = expected.day.to_string();
+        let last = lines.iter().rposition(|line| line.split_whitespace().next() == Some(day.as_str()));
+        match last {
+            Some(i) => lines.insert(i + 1, expected.to_line()),
+            None => {
+                if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(expected.to_line());
+            },
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("# comment\n\n5 1 input_test.txt 35\n8 1 input_test3.txt -\n").unwrap();
+        assert_eq!(answers.get(5, Part::One, "input_test.txt").unwrap().answer.as_deref(), Some("35"));
+        assert_eq!(answers.get(8, Part::One, "input_test3.txt").unwrap().answer, None);
+        assert_eq!(answers.get(5, Part::Two, "input_test.txt"), None);
+
+        assert!(Answers::parse("5 1 input_test.txt").is_err());
+        assert!(Answers::parse("5 3 input_test.txt 35").is_err());
+        assert!(Answers::parse("5 1 input_test.txt 35\n5 1 input_test.txt 36").is_err());
+    }
+
+    #[test]
+    fn test_insert() {
+        let text = "# header\n\n5     1    input.txt        1\n5     1    input_test.txt   35\n\n6     1    input.txt        2\n";
+        let new = |day, part, input: &str, answer: Option<&str>| Expected { day, part, input: input.to_string(), answer: answer.map(str::to_string) };
+
+        let updated = insert(text, &[new(5, Part::Two, "input_test.txt", Some("46")), new(12, Part::One, "input_test.txt", None)]);
+        assert_eq!(updated, "# header\n\n5     1    input.txt        1\n5     1    input_test.txt   35\n5     2    input_test.txt   46\n\n\
+                             6     1    input.txt        2\n\n12    1    input_test.txt   -\n");
+        assert_eq!(Answers::parse(&updated).unwrap().entries.len(), 5);
+    }
+}
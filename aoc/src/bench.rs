@@ -0,0 +1,165 @@
+use std::time::{Duration, Instant};
+
+use common::config::Params;
+use common::input::Source;
+use serde::Serialize;
+
+use crate::days::{Day, Part};
+use crate::report::format_duration;
+use crate::verify::catch_panic;
+
+// The separately timed steps of solving a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+    // both parts while reading the input, for days that can
+    Stream,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Parse, Phase::Part1, Phase::Part2, Phase::Stream];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+            Phase::Stream => "stream",
+        }
+    }
+}
+
+// Wall time of one phase over a number of runs, in nanoseconds so the JSON
+// stays plain integers, and the size of the input it went through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Timing {
+    pub day: u32,
+    pub phase: Phase,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+    pub bytes: u64,
+}
+
+impl Timing {
+    // Summarise the samples, which must not be empty. With an even number
+    // of runs the upper of the two middle samples is the median.
+    pub fn new(day: u32, phase: Phase, bytes: u64, samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let nanos = |d: Duration| d.as_nanos() as u64;
+
+        Timing {
+            day,
+            phase,
+            runs: samples.len(),
+            min_ns: nanos(samples[0]),
+            median_ns: nanos(samples[samples.len() / 2]),
+            max_ns: nanos(samples[samples.len() - 1]),
+            bytes,
+        }
+    }
+
+    // Megabytes of input per second at the median time.
+    pub fn throughput(&self) -> f64 {
+        self.bytes as f64 / 1e6 / (self.median_ns.max(1) as f64 / 1e9)
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+// Time parsing the input and then each part on the parsed input with the
+// given parameters, every phase runs times (at least once). Days that can
+// solve while reading the input are timed doing that too, reading from
+// memory so it's the solving that's timed rather than the disk.
+pub fn bench_day(day: &Day, source: &Source, params: &Params, runs: usize) -> Result<Vec<Timing>, String> {
+    let runs = runs.max(1);
+    let input = source.read()?;
+    let bytes = input.len() as u64;
+
+    let mut samples = Vec::with_capacity(runs);
+    let mut solved = None;
+    for _ in 0..runs {
+        let (result, elapsed) = time(|| (day.parse)(&input));
+        solved = Some(result.map_err(|e| e.to_string())?);
+        samples.push(elapsed);
+    }
+    let mut solved = solved.unwrap();
+    solved.configure(params)?;
+    let mut timings = vec![Timing::new(day.number, Phase::Parse, bytes, &mut samples)];
+
+    let mut answers = Vec::new();
+    for (phase, part) in [(Phase::Part1, Part::One), (Phase::Part2, Part::Two)] {
+        let mut samples = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let (answer, elapsed) = time(|| catch_panic(|| solved.solve(part)).and_then(|answer| answer));
+            samples.push(elapsed);
+            answers.push(answer.map_err(|e| format!("part {}: {}", part, e))?);
+        }
+        timings.push(Timing::new(day.number, phase, bytes, &mut samples));
+    }
+
+    if let Some(stream) = day.stream {
+        let mut samples = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let (streamed, elapsed) = time(|| stream(&mut input.as_bytes(), params));
+            let streamed = streamed?;
+            if streamed != [answers[0].clone(), answers[runs].clone()] {
+                return Err(format!("streaming gave {} and {}, not {} and {}", streamed[0], streamed[1], answers[0], answers[runs]));
+            }
+            samples.push(elapsed);
+        }
+        timings.push(Timing::new(day.number, Phase::Stream, bytes, &mut samples));
+    }
+
+    Ok(timings)
+}
+
+pub fn print_table(timings: &[Timing]) {
+    println!("Day | Phase  |        Min |     Median |        Max | Runs |  Throughput");
+    println!("----+--------+------------+------------+------------+------+------------");
+
+    for t in timings {
+        let format = |ns: u64| format_duration(Duration::from_nanos(ns));
+        println!("{:>3} | {:<6} | {:>10} | {:>10} | {:>10} | {:>4} | {:>6.1} MB/s",
+                 t.day, t.phase.name(), format(t.min_ns), format(t.median_ns), format(t.max_ns), t.runs, t.throughput());
+    }
+}
+
+pub fn to_json(timings: &[Timing]) -> String {
+    serde_json::to_string_pretty(timings).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing() {
+        let ms = Duration::from_millis;
+        let mut samples = vec![ms(5), ms(1), ms(3), ms(9)];
+        let timing = Timing::new(6, Phase::Part2, 20_000, &mut samples);
+        assert_eq!((timing.min_ns, timing.median_ns, timing.max_ns, timing.runs), (1_000_000, 5_000_000, 9_000_000, 4));
+        assert_eq!(timing.throughput(), 4.0);
+
+        assert_eq!(to_json(&[timing]).replace([' ', '\n'], ""),
+                   r#"[{"day":6,"phase":"part2","runs":4,"min_ns":1000000,"median_ns":5000000,"max_ns":9000000,"bytes":20000}]"#);
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let day = crate::days::find(1).unwrap();
+        let source = Source::in_dir(day.dir(), "input_test2.txt");
+        let timings = bench_day(day, &source, &Params::default(), 2).unwrap();
+        let phases: Vec<Phase> = timings.iter().map(|t| t.phase).collect();
+        assert_eq!(phases, Phase::ALL);
+        assert!(timings.iter().all(|t| t.bytes == 93 && t.runs == 2));
+    }
+}
@@ -0,0 +1,482 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use common::input::Source;
+use common::logger::Filter;
+
+use crate::days::Part;
+use crate::parallel;
+use crate::report::Format;
+
+pub const USAGE: &str = "\
+usage: aoc [--log SPEC | --trace] run <day> [--part 1|2] [--input PATH] [--format FORMAT]
+       aoc [--log SPEC | --trace] run --all [--parallel | --jobs N] [--format FORMAT]
+       aoc [--log SPEC | --trace] verify [<day>]
+       aoc [--log SPEC | --trace] bench <day>|--all [--runs N] [--input PATH] [--json PATH]
+       aoc [--log SPEC | --trace] watch <day>
+       aoc record <day> [--part 1|2] [--input PATH] [--cast PATH] [--delay MS]
+       aoc replay <CAST>
+       aoc new <day>
+       aoc fetch <day>
+       aoc examples <day> <PAGE>
+       aoc submit <day> <part>
+
+SPEC is a level (quiet, info, debug, trace) optionally followed by per-day
+levels, e.g. 'info,10=trace'. It defaults to $AOC_LOG, or quiet.
+
+Days read their inputs and puzzle parameters from aoc.toml in the workspace,
+if there is one. --set [dayN.]NAME=VALUE, given anywhere and as often as
+needed, overrides a setting for this run, e.g. '--set day11.expansion=10' or
+just '--set expansion=10' when running day 11. verify and examples always
+use the puzzle's own parameters.
+
+An input PATH of - reads the input from stdin.
+
+FORMAT is text (the default), json or jsonl. The JSON formats include the
+input's path and SHA-256 and the parse and solve times in nanoseconds.
+
+--parallel runs every day and part at once on a thread per CPU, or N threads
+with --jobs, then prints each solver's log output and the results in order
+along with the wall time and the CPU time spent in total.
+
+bench times parsing and each part separately, RUNS times each (default 10),
+and for days that can solve while reading their input (day 1), doing that
+as well. Throughput is the input's size over the median time. --json writes
+the results as JSON to PATH instead, or to stdout if PATH is -.
+
+watch solves every input*.txt of a day again whenever one of them is saved,
+showing the answers and timings, or the parse error.
+
+record runs a day with its visualizations recorded (day 8's walk, day 10's
+maps and flood fill) and pages through the frames, or with --cast saves them
+as an asciicast v2 file showing each frame for MS milliseconds (default 50).
+replay pages through a saved cast.
+
+new creates the dayN crate with a parse/part1/part2 skeleton and test stubs.
+
+fetch downloads a day's input.txt unless it already exists, logging in with
+$AOC_SESSION or the token in ~/.config/aoc/session.
+
+examples reads a puzzle page saved from the site as PAGE, writes each
+example it shows to input_test*.txt in the day's directory and adds the
+example answers it gives to answers.txt.
+
+submit posts the solver's answer and keeps a history in submissions.txt, so
+an answer already known to be wrong is never sent twice.";
+
+pub const DEFAULT_RUNS: usize = 10;
+pub const DEFAULT_DELAY: Duration = Duration::from_millis(50);
+
+// A command along with the options that apply to every command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    // None means fall back to $AOC_LOG.
+    pub log: Option<Filter>,
+    // --set overrides for aoc.toml, in the order given
+    pub settings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    // Run a single day, optionally restricted to one part and/or reading a
+    // different input file.
+    Run { day: u32, part: Option<Part>, input: Option<Source>, format: Format },
+    // Run both parts of every day on their default inputs, one after the
+    // other or on a pool of this many threads.
+    RunAll { format: Format, jobs: Option<usize> },
+    // Check every input of one or all days against the expected answers.
+    Verify { day: Option<u32> },
+    // Time each phase of one day (or every day if None) over several runs.
+    Bench { day: Option<u32>, runs: usize, input: Option<Source>, json: Option<PathBuf> },
+    // Solve every input of a day again each time one changes.
+    Watch { day: u32 },
+    // Run a day while recording its frames, to save as a cast or page
+    // through straight away.
+    Record { day: u32, part: Option<Part>, input: Option<Source>, cast: Option<PathBuf>, delay: Duration },
+    // Page through a saved cast.
+    Replay { cast: PathBuf },
+    // Create the crate for a new day.
+    New { day: u32 },
+    // Download a day's puzzle input.
+    Fetch { day: u32 },
+    // Save the examples on a downloaded puzzle page and register their
+    // answers.
+    Examples { day: u32, page: PathBuf },
+    // Post the solver's answer for one part to the site.
+    Submit { day: u32, part: Part },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\n\n{}", self.0, USAGE)
+    }
+}
+
+fn usage_error<T>(msg: impl Into<String>) -> Result<T, UsageError> {
+    Err(UsageError(msg.into()))
+}
+
+// Parse the arguments following the program name. The logging options and
+// --set may appear anywhere on the line.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Args, UsageError> {
+    let mut args = args.into_iter();
+    let mut log = None;
+    let mut settings = Vec::new();
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trace" => log = Some(Filter { default: log::LevelFilter::Trace, days: Vec::new() }),
+            "--log" => {
+                log = match args.next().map(|s| s.parse::<Filter>()) {
+                    Some(Ok(filter)) => Some(filter),
+                    Some(Err(e)) => return usage_error(e),
+                    None => return usage_error("--log needs a value"),
+                }
+            },
+            "--set" => match args.next() {
+                Some(setting) if setting.contains('=') => settings.push(setting),
+                Some(setting) => return usage_error(format!("--set expects NAME=VALUE, not '{}'", setting)),
+                None => return usage_error("--set needs a value"),
+            },
+            _ => rest.push(arg),
+        }
+    }
+
+    let mut rest = rest.into_iter();
+    let command = match rest.next().as_deref() {
+        Some("run") => parse_run(rest)?,
+        Some("verify") => parse_verify(rest)?,
+        Some("bench") => parse_bench(rest)?,
+        Some("watch") => Command::Watch { day: parse_puzzle_day(rest)? },
+        Some("record") => parse_record(rest)?,
+        Some("replay") => parse_replay(rest)?,
+        Some("new") => Command::New { day: parse_puzzle_day(rest)? },
+        Some("fetch") => Command::Fetch { day: parse_puzzle_day(rest)? },
+        Some("examples") => parse_examples(rest)?,
+        Some("submit") => parse_submit(rest)?,
+        Some(other) => return usage_error(format!("unknown command '{}'", other)),
+        None => return usage_error("missing command"),
+    };
+
+    Ok(Args { command, log, settings })
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut jobs = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--parallel" => jobs = Some(parallel::default_threads()),
+            "--jobs" => {
+                jobs = match args.next().map(|s| s.parse::<usize>()) {
+                    Some(Ok(jobs)) if jobs > 0 => Some(jobs),
+                    Some(_) => return usage_error("--jobs needs a positive number"),
+                    None => return usage_error("--jobs needs a value"),
+                }
+            },
+            "--format" => {
+                format = match args.next().map(|s| s.parse::<Format>()) {
+                    Some(Ok(format)) => format,
+                    Some(Err(e)) => return usage_error(e),
+                    None => return usage_error("--format needs a value"),
+                }
+            },
+            "--part" => {
+                part = match args.next().map(|s| s.parse::<Part>()) {
+                    Some(Ok(part)) => Some(part),
+                    Some(Err(e)) => return usage_error(e),
+                    None => return usage_error("--part needs a value"),
+                }
+            },
+            "--input" => {
+                input = match args.next() {
+                    Some(path) => Some(Source::from_arg(&path)),
+                    None => return usage_error("--input needs a path"),
+                }
+            },
+            _ if arg.starts_with("--") => return usage_error(format!("unknown option '{}'", arg)),
+            _ => {
+                if day.is_some() {
+                    return usage_error(format!("unexpected argument '{}'", arg));
+                }
+                day = Some(parse_day(&arg)?);
+            },
+        }
+    }
+
+    if all {
+        if day.is_some() || part.is_some() || input.is_some() {
+            return usage_error("--all can't be combined with a day, --part or --input");
+        }
+        return Ok(Command::RunAll { format, jobs });
+    }
+    if jobs.is_some() {
+        return usage_error("--parallel and --jobs only apply to --all");
+    }
+
+    match day {
+        Some(day) => Ok(Command::Run { day, part, input, format }),
+        None => usage_error("missing day"),
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u32, UsageError> {
+    arg.parse::<u32>().or_else(|_| usage_error(format!("invalid day '{}'", arg)))
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let day = args.next().map(|arg| parse_day(&arg)).transpose()?;
+
+    match args.next() {
+        Some(arg) => usage_error(format!("unexpected argument '{}'", arg)),
+        None => Ok(Command::Verify { day }),
+    }
+}
+
+// The arguments of a command that only takes a day, which must be one of
+// the 25 days of the puzzle.
+fn parse_puzzle_day(mut args: impl Iterator<Item = String>) -> Result<u32, UsageError> {
+    let day = match args.next() {
+        Some(arg) => parse_day(&arg)?,
+        None => return usage_error("missing day"),
+    };
+    if !(1..=25).contains(&day) {
+        return usage_error(format!("there is no day {}", day));
+    }
+
+    match args.next() {
+        Some(arg) => usage_error(format!("unexpected argument '{}'", arg)),
+        None => Ok(day),
+    }
+}
+
+fn parse_record(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut cast = None;
+    let mut delay = DEFAULT_DELAY;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                part = match args.next().map(|s| s.parse::<Part>()) {
+                    Some(Ok(part)) => Some(part),
+                    Some(Err(e)) => return usage_error(e),
+                    None => return usage_error("--part needs a value"),
+                }
+            },
+            "--input" => {
+                input = match args.next() {
+                    Some(path) => Some(Source::from_arg(&path)),
+                    None => return usage_error("--input needs a path"),
+                }
+            },
+            "--cast" => {
+                cast = match args.next() {
+                    Some(path) => Some(PathBuf::from(path)),
+                    None => return usage_error("--cast needs a path"),
+                }
+            },
+            "--delay" => {
+                delay = match args.next().map(|s| s.parse::<u64>()) {
+                    Some(Ok(ms)) => Duration::from_millis(ms),
+                    Some(Err(_)) => return usage_error("--delay needs a number of milliseconds"),
+                    None => return usage_error("--delay needs a value"),
+                }
+            },
+            _ if arg.starts_with("--") => return usage_error(format!("unknown option '{}'", arg)),
+            _ => {
+                if day.is_some() {
+                    return usage_error(format!("unexpected argument '{}'", arg));
+                }
+                day = Some(parse_day(&arg)?);
+            },
+        }
+    }
+
+    match day {
+        Some(day) => Ok(Command::Record { day, part, input, cast, delay }),
+        None => usage_error("missing day"),
+    }
+}
+
+fn parse_replay(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let Some(cast) = args.next() else {
+        return usage_error("replay needs a cast file");
+    };
+
+    match args.next() {
+        Some(arg) => usage_error(format!("unexpected argument '{}'", arg)),
+        None => Ok(Command::Replay { cast: PathBuf::from(cast) }),
+    }
+}
+
+fn parse_examples(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let (Some(day), Some(page)) = (args.next(), args.next()) else {
+        return usage_error("examples needs a day and a saved puzzle page");
+    };
+    let day = parse_day(&day)?;
+
+    match args.next() {
+        Some(arg) => usage_error(format!("unexpected argument '{}'", arg)),
+        None => Ok(Command::Examples { day, page: PathBuf::from(page) }),
+    }
+}
+
+fn parse_submit(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let (Some(day), Some(part)) = (args.next(), args.next()) else {
+        return usage_error("submit needs a day and a part");
+    };
+    let day = parse_day(&day)?;
+    let part = part.parse::<Part>().or_else(usage_error)?;
+
+    match args.next() {
+        Some(arg) => usage_error(format!("unexpected argument '{}'", arg)),
+        None => Ok(Command::Submit { day, part }),
+    }
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut all = false;
+    let mut day = None;
+    let mut runs = DEFAULT_RUNS;
+    let mut input = None;
+    let mut json = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--runs" => {
+                runs = match args.next().map(|s| s.parse::<usize>()) {
+                    Some(Ok(runs)) if runs > 0 => runs,
+                    Some(_) => return usage_error("--runs needs a positive number"),
+                    None => return usage_error("--runs needs a value"),
+                }
+            },
+            "--input" => {
+                input = match args.next() {
+                    Some(path) => Some(Source::from_arg(&path)),
+                    None => return usage_error("--input needs a path"),
+                }
+            },
+            "--json" => {
+                json = match args.next() {
+                    Some(path) => Some(PathBuf::from(path)),
+                    None => return usage_error("--json needs a path"),
+                }
+            },
+            _ if arg.starts_with("--") => return usage_error(format!("unknown option '{}'", arg)),
+            _ => {
+                if day.is_some() {
+                    return usage_error(format!("unexpected argument '{}'", arg));
+                }
+                day = Some(parse_day(&arg)?);
+            },
+        }
+    }
+
+    if all {
+        if day.is_some() || input.is_some() {
+            return usage_error("--all can't be combined with a day or --input");
+        }
+    } else if day.is_none() {
+        return usage_error("missing day");
+    }
+
+    Ok(Command::Bench { day, runs, input, json })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_full(args: &[&str]) -> Result<Args, UsageError> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    fn parse(args: &[&str]) -> Result<Command, UsageError> {
+        parse_full(args).map(|args| args.command)
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(parse(&["run", "7"]), Ok(Command::Run { day: 7, part: None, input: None, format: Format::Text }));
+        assert_eq!(parse(&["run", "10", "--part", "2", "--input", "day10/input_test.txt", "--format", "json"]),
+                   Ok(Command::Run { day: 10, part: Some(Part::Two), input: Some(Source::from_arg("day10/input_test.txt")), format: Format::Json }));
+        assert_eq!(parse(&["run", "3", "--input", "-"]), Ok(Command::Run { day: 3, part: None, input: Some(Source::Stdin), format: Format::Text }));
+        assert_eq!(parse(&["run", "--all"]), Ok(Command::RunAll { format: Format::Text, jobs: None }));
+        assert_eq!(parse(&["run", "--all", "--format", "jsonl"]), Ok(Command::RunAll { format: Format::JsonLines, jobs: None }));
+        assert_eq!(parse(&["run", "--all", "--jobs", "4"]), Ok(Command::RunAll { format: Format::Text, jobs: Some(4) }));
+        assert!(matches!(parse(&["run", "--parallel", "--all"]), Ok(Command::RunAll { jobs: Some(_), .. })));
+        assert_eq!(parse(&["verify"]), Ok(Command::Verify { day: None }));
+        assert_eq!(parse(&["verify", "5"]), Ok(Command::Verify { day: Some(5) }));
+        assert_eq!(parse(&["bench", "6", "--runs", "50", "--json", "-"]),
+                   Ok(Command::Bench { day: Some(6), runs: 50, input: None, json: Some(PathBuf::from("-")) }));
+        assert_eq!(parse(&["fetch", "12"]), Ok(Command::Fetch { day: 12 }));
+        assert_eq!(parse(&["watch", "9"]), Ok(Command::Watch { day: 9 }));
+        assert_eq!(parse(&["record", "10", "--part", "2", "--cast", "fill.cast", "--delay", "20"]),
+                   Ok(Command::Record { day: 10, part: Some(Part::Two), input: None, cast: Some(PathBuf::from("fill.cast")), delay: Duration::from_millis(20) }));
+        assert_eq!(parse(&["record", "8"]), Ok(Command::Record { day: 8, part: None, input: None, cast: None, delay: DEFAULT_DELAY }));
+        assert_eq!(parse(&["replay", "fill.cast"]), Ok(Command::Replay { cast: PathBuf::from("fill.cast") }));
+        assert_eq!(parse(&["new", "12"]), Ok(Command::New { day: 12 }));
+        assert_eq!(parse(&["examples", "5", "day5.html"]), Ok(Command::Examples { day: 5, page: PathBuf::from("day5.html") }));
+        assert_eq!(parse(&["submit", "5", "2"]), Ok(Command::Submit { day: 5, part: Part::Two }));
+        assert_eq!(parse(&["bench", "--all"]), Ok(Command::Bench { day: None, runs: DEFAULT_RUNS, input: None, json: None }));
+    }
+
+    #[test]
+    fn test_bad_args() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "seven"]).is_err());
+        assert!(parse(&["run", "7", "--part", "3"]).is_err());
+        assert!(parse(&["run", "--all", "7"]).is_err());
+        assert!(parse(&["run", "7", "--format", "xml"]).is_err());
+        assert!(parse(&["run", "7", "--parallel"]).is_err());
+        assert!(parse(&["run", "--all", "--jobs", "0"]).is_err());
+        assert!(parse(&["verify", "5", "6"]).is_err());
+        assert!(parse(&["bench"]).is_err());
+        assert!(parse(&["fetch", "26"]).is_err());
+        assert!(parse(&["new", "0"]).is_err());
+        assert!(parse(&["watch"]).is_err());
+        assert!(parse(&["record", "10", "--delay", "soon"]).is_err());
+        assert!(parse(&["replay"]).is_err());
+        assert!(parse(&["submit", "5"]).is_err());
+        assert!(parse(&["examples", "5"]).is_err());
+        assert!(parse(&["submit", "5", "3"]).is_err());
+        assert!(parse(&["bench", "6", "--runs", "0"]).is_err());
+        assert!(parse(&["bench", "--all", "--input", "x.txt"]).is_err());
+        assert!(parse(&["run", "7", "--log"]).is_err());
+        assert!(parse(&["run", "7", "--log", "loud"]).is_err());
+        assert!(parse(&["run", "7", "--set"]).is_err());
+        assert!(parse(&["run", "7", "--set", "expansion"]).is_err());
+    }
+
+    #[test]
+    fn test_log() {
+        assert_eq!(parse_full(&["run", "7"]).unwrap().log, None);
+        assert_eq!(parse_full(&["--trace", "run", "7"]).unwrap().log, Some("trace".parse().unwrap()));
+        assert_eq!(parse_full(&["verify", "--log", "info,10=trace"]).unwrap(),
+                   Args { command: Command::Verify { day: None }, log: Some("info,day10=trace".parse().unwrap()), settings: Vec::new() });
+    }
+
+    #[test]
+    fn test_settings() {
+        let args = parse_full(&["--set", "day2.max_red=20", "run", "11", "--set", "expansion=10"]).unwrap();
+        assert_eq!(args.command, Command::Run { day: 11, part: None, input: None, format: Format::Text });
+        assert_eq!(args.settings, ["day2.max_red=20", "expansion=10"]);
+    }
+}
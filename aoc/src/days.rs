@@ -0,0 +1,194 @@
+use std::fmt;
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use common::config::{self, Config, DayConfig};
+use common::input::Source;
+use common::{ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part '{}', expected 1 or 2", s)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+// A day's input after parsing, ready to solve either part with the
+// answer converted to a string for display, or the reason there isn't one.
+// Until configured, it solves with the puzzle's own parameters.
+pub trait Solve {
+    fn configure(&mut self, params: &config::Params) -> Result<(), String>;
+    fn solve(&self, part: Part) -> Result<String, String>;
+}
+
+struct Parsed<I, P> {
+    input: I,
+    params: P,
+    from_config: fn(&config::Params) -> Result<P, String>,
+    part1: fn(&I, &P) -> Result<String, String>,
+    part2: fn(&I, &P) -> Result<String, String>,
+}
+
+impl<I, P> Solve for Parsed<I, P> {
+    fn configure(&mut self, params: &config::Params) -> Result<(), String> {
+        self.params = (self.from_config)(params)?;
+        Ok(())
+    }
+
+    fn solve(&self, part: Part) -> Result<String, String> {
+        match part {
+            Part::One => (self.part1)(&self.input, &self.params),
+            Part::Two => (self.part2)(&self.input, &self.params),
+        }
+    }
+}
+
+// Solves both parts while reading the input, for the days that can.
+pub type Stream = fn(&mut dyn BufRead, &config::Params) -> Result<[String; 2], String>;
+
+pub struct Day {
+    pub number: u32,
+    pub parse: fn(&str) -> Result<Box<dyn Solve>, ParseError>,
+    pub stream: Option<Stream>,
+}
+
+impl Day {
+    // Directory of this day's crate, holding its input.txt and examples.
+    pub fn dir(&self) -> PathBuf {
+        workspace_root().join(format!("day{}", self.number))
+    }
+
+    pub fn default_input(&self) -> Source {
+        Source::in_dir(self.dir(), "input.txt")
+    }
+
+    // The input set in aoc.toml, or else the default one.
+    pub fn input(&self, config: &DayConfig) -> Source {
+        match &config.input {
+            Some(path) => Source::Path(path.clone()),
+            None => self.default_input(),
+        }
+    }
+
+    // The puzzle input and every example (input_test*.txt) in this day's
+    // directory, as file names relative to dir(), sorted.
+    pub fn inputs(&self) -> Vec<String> {
+        inputs_in(&self.dir())
+    }
+}
+
+// Whether a file name is a day's input or one of its examples.
+pub fn is_input_name(name: &str) -> bool {
+    name.starts_with("input") && name.ends_with(".txt")
+}
+
+// The names of the inputs in dir, sorted.
+pub fn inputs_in(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir).
+        into_iter().flatten().flatten().
+        filter_map(|entry| entry.file_name().into_string().ok()).
+        filter(|name| is_input_name(name)).
+        collect();
+    names.sort();
+    names
+}
+
+macro_rules! day {
+    ($number:literal, $krate:ident) => {
+        Day {
+            number: $number,
+            parse: |input| {
+                Ok(Box::new(Parsed {
+                    input: $krate::parse(input)?,
+                    params: (),
+                    from_config: |params| params.only(&[]),
+                    part1: |input, _| $krate::part1(input).answer(),
+                    part2: |input, _| $krate::part2(input).answer(),
+                }))
+            },
+            stream: None,
+        }
+    };
+    // a day whose Params can be set in aoc.toml
+    ($number:literal, $krate:ident, Params) => {
+        Day {
+            number: $number,
+            parse: |input| {
+                Ok(Box::new(Parsed {
+                    input: $krate::parse(input)?,
+                    params: $krate::Params::default(),
+                    from_config: $krate::Params::from_config,
+                    part1: |input, params| $krate::part1_with(input, params).answer(),
+                    part2: |input, params| $krate::part2_with(input, params).answer(),
+                }))
+            },
+            stream: None,
+        }
+    };
+    // and that has a stream::solve() as well
+    ($number:literal, $krate:ident, Params, Stream) => {
+        Day {
+            stream: Some(|reader, params| {
+                let params = $krate::Params::from_config(params)?;
+                let totals = $krate::stream::solve(reader, &params).map_err(|e| e.to_string())?;
+                Ok([totals.part1.to_string(), totals.part2.to_string()])
+            }),
+            ..day!($number, $krate, Params)
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(1, day1, Params, Stream),
+    day!(2, day2, Params),
+    day!(3, day3),
+    day!(4, day4),
+    day!(5, day5),
+    day!(6, day6),
+    day!(7, day7),
+    day!(8, day8, Params),
+    day!(9, day9),
+    day!(10, day10),
+    day!(11, day11, Params),
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+// aoc.toml in the workspace, or the defaults if there isn't one.
+pub fn load_config() -> Result<Config, String> {
+    Config::load(&workspace_root().join(config::FILE_NAME))
+}
+
+// The day crates all live next to this one, so we can find them (and their
+// inputs) no matter which directory we were started from.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
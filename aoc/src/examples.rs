@@ -0,0 +1,324 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::answers::{self, Answers, Expected};
+use crate::days::{self, Part};
+
+// An example input from the puzzle page, with the answer the page gives for
+// each part (None where it doesn't give one).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub text: String,
+    pub answers: [Option<String>; 2],
+}
+
+// What the page holds: the examples in the order they appear, and how many
+// parts it describes (2 once part 2 is unlocked).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub examples: Vec<Example>,
+    pub parts: usize,
+}
+
+enum Token {
+    // a <pre><code> block
+    Block(String),
+    // an emphasized <code>, which is how the page marks an example's answer
+    Answer(String),
+}
+
+const BLOCK: (&str, &str) = ("<pre><code>", "</code></pre>");
+const ANSWERS: [(&str, &str); 2] = [("<code><em>", "</em></code>"), ("<em><code>", "</code></em>")];
+
+// The blocks and answers in html, in order.
+fn tokens(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    loop {
+        let next = [BLOCK, ANSWERS[0], ANSWERS[1]].into_iter().
+            enumerate().
+            filter_map(|(kind, (open, close))| rest.find(open).map(|at| (at, kind, open, close))).
+            min();
+        let Some((at, kind, open, close)) = next else {
+            break;
+        };
+
+        let start = at + open.len();
+        let Some(len) = rest[start..].find(close) else {
+            break;
+        };
+        let text = unescape(&strip_tags(&rest[start..start + len]));
+        tokens.push(if kind == 0 { Token::Block(text) } else { Token::Answer(text) });
+        rest = &rest[start + len + close.len()..];
+    }
+
+    tokens
+}
+
+// Drop any tags, such as the <em> highlighting inside an example, keeping
+// the text and its whitespace as they are.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<").
+        replace("&gt;", ">").
+        replace("&quot;", "\"").
+        replace("&#39;", "'").
+        replace("&apos;", "'").
+        replace("&amp;", "&")
+}
+
+// Each part of the puzzle is an <article>. A page without any is taken to be
+// a single part.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        articles.push(&article[..end]);
+        rest = &article[end..];
+    }
+
+    if articles.is_empty() {
+        articles.push(html);
+    }
+    articles
+}
+
+// Pull the examples out of a saved puzzle page. Only blocks that is_input
+// accepts count as examples, which keeps out most of the blocks that just
+// illustrate the working, and a block quoting part of an earlier example
+// only points back at it. An emphasized value belongs to the latest example
+// before it, if need be from part 1, and the last one wins, since the page
+// tends to build up to the answer.
+pub fn extract(html: &str, is_input: impl Fn(&str) -> bool) -> Page {
+    let articles = articles(html);
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (part, article) in articles.iter().take(2).enumerate() {
+        let mut current = examples.len().checked_sub(1);
+
+        for token in tokens(article) {
+            match token {
+                // an empty block would seem to quote the first example
+                Token::Block(text) if text.trim().is_empty() => {},
+                Token::Block(text) if is_input(&text) => {
+                    let quoted = examples.iter().position(|example| example.text.contains(text.trim()));
+                    current = match quoted {
+                        Some(i) => Some(i),
+                        None => {
+                            examples.push(Example { text, answers: [None, None] });
+                            Some(examples.len() - 1)
+                        },
+                    };
+                },
+                Token::Block(_) => {},
+                Token::Answer(answer) => if let Some(i) = current {
+                    examples[i].answers[part] = Some(answer);
+                },
+            }
+        }
+    }
+
+    // blocks never followed by an answer weren't examples after all
+    examples.retain(|example| example.answers.iter().any(Option::is_some));
+
+    Page { examples, parts: articles.len().min(2) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Wrote(String),
+    // an example file with the same text was already there
+    Kept(String),
+    Registered(Expected),
+    // the registry already has a different answer, which was left alone
+    Conflict { expected: Expected, registered: String },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Wrote(name) => write!(f, "wrote {}", name),
+            Change::Kept(name) => write!(f, "{} already has this example", name),
+            Change::Registered(expected) => write!(f, "registered {}", expected.to_line()),
+            Change::Conflict { expected, registered } => write!(f,
+                "not registering {}: answers.txt says {}", expected.to_line(), registered),
+        }
+    }
+}
+
+// The example file names in order: input_test.txt, input_test2.txt, ...
+fn example_name(n: usize) -> String {
+    if n == 1 { "input_test.txt".to_string() } else { format!("input_test{}.txt", n) }
+}
+
+// Write each example to the day's directory, reusing a file that already
+// holds the same text and otherwise taking the next free number, then add
+// their answers to the registry at answers_path. A part the page describes
+// but gives no answer for on an example is registered as not applying.
+pub fn save(page: &Page, day: u32, dir: &Path, answers_path: &Path) -> Result<Vec<Change>, String> {
+    let registry_text = match fs::read_to_string(answers_path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("couldn't read {}: {}", answers_path.display(), e)),
+    };
+    let registry = Answers::parse(&registry_text).map_err(|e| format!("{}: {}", answers_path.display(), e))?;
+
+    let mut changes = Vec::new();
+    let mut new = Vec::new();
+    for example in &page.examples {
+        let text = if example.text.ends_with('\n') { example.text.clone() } else { format!("{}\n", example.text) };
+
+        let existing = days::inputs_in(dir).into_iter().
+            filter(|name| name.starts_with("input_test")).
+            find(|name| fs::read_to_string(dir.join(name)).is_ok_and(|contents| contents == text));
+        let name = match existing {
+            Some(name) => {
+                changes.push(Change::Kept(name.clone()));
+                name
+            },
+            None => {
+                let name = (1..).map(example_name).find(|name| !dir.join(name).exists()).unwrap();
+                fs::write(dir.join(&name), &text).
+                    map_err(|e| format!("couldn't write {}: {}", dir.join(&name).display(), e))?;
+                changes.push(Change::Wrote(name.clone()));
+                name
+            },
+        };
+
+        for (i, part) in Part::BOTH.into_iter().enumerate().take(page.parts) {
+            let expected = Expected { day, part, input: name.clone(), answer: example.answers[i].clone() };
+            match registry.get(day, part, &name) {
+                None => {
+                    changes.push(Change::Registered(expected.clone()));
+                    new.push(expected);
+                },
+                // a real answer says more than "doesn't apply"
+                Some(registered) if registered.answer == expected.answer || expected.answer.is_none() => {},
+                Some(registered) => changes.push(Change::Conflict {
+                    expected,
+                    registered: registered.answer.clone().unwrap_or_else(|| "-".to_string()),
+                }),
+            }
+        }
+    }
+
+    if !new.is_empty() {
+        fs::write(answers_path, answers::insert(&registry_text, &new)).
+            map_err(|e| format!("couldn't write {}: {}", answers_path.display(), e))?;
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>The second line is</p>
+<pre><code>pqr3stu8vwx</code></pre>
+<p>The values are <code>12</code> and <code>38</code>. Adding these together produces <code><em>50</em></code>.</p>
+<p>Working it out:</p>
+<pre><code>12 + 38 = <em>50</em>
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>54597</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Letters count &amp; more:</p>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<p>Adding these together produces <em><code>29</code></em> and then <code><em>112</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    fn is_input(text: &str) -> bool {
+        !text.contains('=')
+    }
+
+    #[test]
+    fn test_extract() {
+        let page = extract(PAGE, is_input);
+        assert_eq!(page.parts, 2);
+        assert_eq!(page.examples, [
+            Example { text: "1abc2\npqr3stu8vwx\n".to_string(), answers: [Some("50".to_string()), None] },
+            Example { text: "two1nine\neightwothree\n".to_string(), answers: [None, Some("112".to_string())] },
+        ]);
+
+        // before part 2 is unlocked
+        let part1 = &PAGE[..PAGE.find("<p>Your puzzle answer").unwrap()];
+        assert_eq!(extract(part1, is_input).parts, 1);
+    }
+
+    #[test]
+    fn test_extract_empty_block() {
+        let page = r#"<article class="day-desc"><p>For example:</p>
+<pre><code>1abc2
+</code></pre>
+<p>That gives <code><em>12</em></code>. Or:</p>
+<pre><code>pqr3stu8vwx
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>54597</code>.</p>
+<article class="day-desc"><p>Nothing to see here:</p>
+<pre><code>
+ </code></pre>
+<p>That one gives <code><em>38</em></code>.</p>
+</article>"#;
+        let page = extract(page, is_input);
+        assert_eq!(page.examples.len(), 2);
+        assert_eq!(page.examples[0].answers, [Some("12".to_string()), None]);
+        assert_eq!(page.examples[1].answers, [None, Some("38".to_string())]);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let answers_path = dir.path().join("answers.txt");
+        fs::write(&answers_path, "1     1    input.txt        54597\n").unwrap();
+        fs::write(dir.path().join("input_test.txt"), "two1nine\neightwothree\n").unwrap();
+        let page = extract(PAGE, is_input);
+
+        let changes = save(&page, 1, dir.path(), &answers_path).unwrap();
+        assert_eq!(changes[0], Change::Wrote("input_test2.txt".to_string()));
+        assert_eq!(changes[3], Change::Kept("input_test.txt".to_string()));
+        assert_eq!(fs::read_to_string(dir.path().join("input_test2.txt")).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+        let registry = Answers::load(&answers_path).unwrap();
+        assert_eq!(registry.get(1, Part::One, "input_test2.txt").unwrap().answer.as_deref(), Some("50"));
+        assert_eq!(registry.get(1, Part::Two, "input_test2.txt").unwrap().answer, None);
+        assert_eq!(registry.get(1, Part::One, "input_test.txt").unwrap().answer, None);
+        assert_eq!(registry.get(1, Part::Two, "input_test.txt").unwrap().answer.as_deref(), Some("112"));
+
+        // running it again changes nothing
+        let again = save(&page, 1, dir.path(), &answers_path).unwrap();
+        assert!(again.iter().all(|change| matches!(change, Change::Kept(_))));
+
+        // but a different answer is reported rather than overwritten
+        let mut changed = page.clone();
+        changed.examples[0].answers[0] = Some("51".to_string());
+        let conflicts = save(&changed, 1, dir.path(), &answers_path).unwrap();
+        assert!(matches!(&conflicts[1], Change::Conflict { registered, .. } if registered == "50"));
+        assert_eq!(Answers::load(&answers_path).unwrap(), registry);
+    }
+}
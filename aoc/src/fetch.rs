@@ -0,0 +1,77 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days;
+use crate::site::{self, Site};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    // the file was already there, so nothing was requested
+    Cached(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    NoToken,
+    // the site didn't accept the token
+    Unauthenticated,
+    NotUnlocked(u32),
+    Status(u16, String),
+    Network(String),
+    Write(PathBuf, String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::NoToken => write!(f,
+                "no session token, set AOC_SESSION or put it in {}", site::token_path().display()),
+            FetchError::Unauthenticated => write!(f,
+                "the session token was rejected, it has probably expired; log in again and copy the new session cookie"),
+            FetchError::NotUnlocked(day) => write!(f, "day {} isn't unlocked yet", day),
+            FetchError::Status(status, body) => write!(f, "unexpected response {}: {}", status, body.trim()),
+            FetchError::Network(e) => write!(f, "request failed: {}", e),
+            FetchError::Write(path, e) => write!(f, "couldn't write {}: {}", path.display(), e),
+        }
+    }
+}
+
+// Where a day's puzzle input is kept.
+pub fn input_path(day: u32) -> PathBuf {
+    days::workspace_root().join(format!("day{}", day)).join("input.txt")
+}
+
+// Download a day's input to dest, unless it's already there.
+pub fn fetch_input(site: &Site, day: u32, dest: &Path) -> Result<Fetched, FetchError> {
+    if dest.exists() {
+        return Ok(Fetched::Cached(dest.to_path_buf()));
+    }
+    if site.token.is_none() {
+        return Err(FetchError::NoToken);
+    }
+
+    let response = site.get(&format!("/day/{}/input", day)).map_err(FetchError::Network)?;
+    match response.status {
+        200 => {},
+        // the site answers 400 (and sometimes 500) with "please log in"
+        // when the cookie is missing or stale
+        400 | 401 | 403 => return Err(FetchError::Unauthenticated),
+        500 if response.body.contains("log in") => return Err(FetchError::Unauthenticated),
+        404 => return Err(FetchError::NotUnlocked(day)),
+        status => return Err(FetchError::Status(status, response.body)),
+    }
+
+    // write to a temporary file first so an interrupted fetch never leaves a
+    // partial input behind to be mistaken for a cached one
+    let write_error = |e: std::io::Error| FetchError::Write(dest.to_path_buf(), e.to_string());
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir).map_err(write_error)?;
+    }
+    let partial = dest.with_extension("txt.part");
+    fs::write(&partial, &response.body).map_err(write_error)?;
+    fs::rename(&partial, dest).map_err(write_error)?;
+
+    Ok(Fetched::Downloaded(dest.to_path_buf()))
+}
@@ -0,0 +1,99 @@
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod days;
+pub mod examples;
+pub mod fetch;
+pub mod parallel;
+pub mod report;
+pub mod scaffold;
+pub mod site;
+pub mod submit;
+pub mod verify;
+pub mod watch;
+
+use std::time::Instant;
+
+use common::config::Params;
+use common::input::Source;
+
+use days::{Day, Part};
+use report::Row;
+use verify::catch_panic;
+
+// Solve the given parts of a day with the given parameters, reading its
+// input from source. If the input can't be read or parsed, or the parameters
+// aren't right for the day, every part is reported as failed and the full
+// diagnostic is written to stderr. A part whose solver panics fails too.
+// Parsing and each part are timed separately.
+pub fn run_day(day: &Day, parts: &[Part], source: &Source, params: &Params) -> Vec<Row> {
+    let (rows, diagnostic) = solve_day(day, parts, source, params);
+    if let Some(diagnostic) = diagnostic {
+        eprintln!("{}", diagnostic);
+    }
+    rows
+}
+
+// Like run_day(), but returning the parse diagnostic instead of printing it.
+pub fn solve_day(day: &Day, parts: &[Part], source: &Source, params: &Params) -> (Vec<Row>, Option<String>) {
+    let input = source.read();
+    let input_sha256 = input.as_ref().ok().map(|input| report::sha256(input));
+
+    let mut diagnostic = None;
+    let start = Instant::now();
+    let solved = input.and_then(|input| (day.parse)(&input).map_err(|e| {
+        diagnostic = Some(format!("{}\n  = in {}\n", e.render(&input), source));
+        e.to_string()
+    }));
+    let parse_time = solved.is_ok().then(|| start.elapsed());
+    let solved = solved.and_then(|mut solved| solved.configure(params).map(|()| solved));
+
+    let rows = parts.iter().map(|&part| {
+        let start = Instant::now();
+        let answer = solved.as_ref().
+            map_err(|e| e.clone()).
+            and_then(|solved| catch_panic(|| solved.solve(part)).and_then(|answer| answer));
+
+        Row {
+            day: day.number,
+            part,
+            answer,
+            input: source.clone(),
+            input_sha256: input_sha256.clone(),
+            parse_time,
+            time: start.elapsed(),
+        }
+    }).collect();
+
+    (rows, diagnostic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use days::Solve;
+
+    // A day whose part 2 always panics.
+    struct Panicky;
+
+    impl Solve for Panicky {
+        fn configure(&mut self, _params: &Params) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn solve(&self, part: Part) -> Result<String, String> {
+            match part {
+                Part::One => Ok("1".to_string()),
+                Part::Two => panic!("no answer"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_day_panic() {
+        let day = Day { number: 99, parse: |_| Ok(Box::new(Panicky)), stream: None };
+        let (rows, _) = solve_day(&day, &Part::BOTH, &Source::text("test", ""), &Params::default());
+        assert_eq!(rows[0].answer, Ok("1".to_string()));
+        assert_eq!(rows[1].answer, Err("panicked: no answer".to_string()));
+    }
+}
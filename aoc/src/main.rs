@@ -0,0 +1,387 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+use aoc::answers::{self, Answers};
+use aoc::bench;
+use aoc::examples;
+use aoc::fetch::{self, Fetched};
+use aoc::parallel;
+use aoc::site::Site;
+use aoc::submit::{self, Advice, History, Verdict};
+use aoc::cli::{self, Args, Command};
+use common::config::Config;
+use common::frames::{self, Recording};
+use common::logger;
+use aoc::days::{self, Day, Part};
+use aoc::report;
+use aoc::scaffold;
+use aoc::verify;
+use aoc::watch;
+use common::input::Source;
+
+fn main() {
+    let Args { command, log, settings } = match cli::parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    // Options on the command line win over $AOC_LOG
+    match log {
+        Some(filter) => logger::init(filter),
+        None => if let Err(e) = logger::init_from_env() {
+            eprintln!("{}", e);
+            process::exit(2);
+        },
+    }
+
+    let config = match load_config(&command, &settings) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    let (rows, format) = match command {
+        Command::Verify { day } => process::exit(run_verify(day)),
+        Command::Bench { day, runs, input, json } => process::exit(run_bench(&config, day, runs, input, json)),
+        Command::Watch { day } => process::exit(run_watch(&config, day)),
+        Command::Record { day, part, input, cast, delay } => process::exit(run_record(&config, day, part, input, cast, delay)),
+        Command::Replay { cast } => process::exit(run_replay(&cast)),
+        Command::New { day } => process::exit(run_new(day)),
+        Command::Fetch { day } => process::exit(run_fetch(day)),
+        Command::Examples { day, page } => process::exit(run_examples(day, &page)),
+        Command::Submit { day, part } => process::exit(run_submit(&config, day, part)),
+        Command::Run { day, part, input, format } => {
+            let Some(day) = days::find(day) else {
+                eprintln!("no solver for day {}", day);
+                process::exit(1);
+            };
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::BOTH.to_vec(),
+            };
+            let day_config = config.day(day.number);
+            let source = input.unwrap_or_else(|| day.input(&day_config));
+
+            (aoc::run_day(day, &parts, &source, &day_config.params), format)
+        },
+        Command::RunAll { format, jobs: Some(threads) } => {
+            let summary = parallel::run_jobs(&parallel::all_jobs(&config), threads);
+            parallel::print(&summary, format);
+            let failed = summary.done.iter().any(|done| done.row.answer.is_err());
+            process::exit(if failed { 1 } else { 0 });
+        },
+        Command::RunAll { format, jobs: None } => {
+            let mut rows = Vec::new();
+            for day in days::DAYS.iter() {
+                let day_config = config.day(day.number);
+                rows.extend(aoc::run_day(day, &Part::BOTH, &day.input(&day_config), &day_config.params));
+            }
+            (rows, format)
+        },
+    };
+
+    report::print(&rows, format);
+
+    if rows.iter().any(|row| row.answer.is_err()) {
+        process::exit(1);
+    }
+}
+
+// aoc.toml with the --set overrides applied. A setting without a day is for
+// the one day the command works on, if it has one.
+fn load_config(command: &Command, settings: &[String]) -> Result<Config, String> {
+    let mut config = days::load_config()?;
+    let only_day = match command {
+        Command::Run { day, .. } | Command::Watch { day } | Command::Record { day, .. } | Command::Submit { day, .. } => Some(*day),
+        Command::Bench { day, .. } => *day,
+        _ => None,
+    };
+    for setting in settings {
+        config.set(setting, only_day).map_err(|e| format!("--set {}: {}", setting, e))?;
+    }
+    Ok(config)
+}
+
+// Check the solvers against the answer registry, returning the exit code.
+fn run_verify(day: Option<u32>) -> i32 {
+    let answers = match Answers::load(&answers::default_path()) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    let to_check: Vec<&Day> = match day {
+        Some(number) => match days::find(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("no solver for day {}", number);
+                return 1;
+            }
+        },
+        None => days::DAYS.iter().collect(),
+    };
+
+    let checks: Vec<verify::Check> = to_check.iter().
+        flat_map(|day| verify::verify_day(day, &answers)).
+        collect();
+
+    verify::print_report(&checks);
+
+    if checks.iter().any(|c| matches!(c.status, verify::Status::Fail(_))) { 1 } else { 0 }
+}
+
+// Time one or all days and print or save the results, returning the exit code.
+fn run_bench(config: &Config, day: Option<u32>, runs: usize, input: Option<Source>, json: Option<PathBuf>) -> i32 {
+    let to_bench: Vec<&Day> = match day {
+        Some(number) => match days::find(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("no solver for day {}", number);
+                return 1;
+            }
+        },
+        None => days::DAYS.iter().collect(),
+    };
+
+    let mut timings = Vec::new();
+    for day in to_bench {
+        let day_config = config.day(day.number);
+        let source = input.clone().unwrap_or_else(|| day.input(&day_config));
+        match bench::bench_day(day, &source, &day_config.params, runs) {
+            Ok(day_timings) => timings.extend(day_timings),
+            Err(e) => {
+                eprintln!("day {}: {}", day.number, e);
+                return 1;
+            }
+        }
+    }
+
+    match json {
+        None => bench::print_table(&timings),
+        Some(path) if path.as_os_str() == "-" => println!("{}", bench::to_json(&timings)),
+        Some(path) => if let Err(e) = fs::write(&path, bench::to_json(&timings) + "\n") {
+            eprintln!("couldn't write {}: {}", path.display(), e);
+            return 1;
+        },
+    }
+
+    0
+}
+
+fn run_fetch(day: u32) -> i32 {
+    let path = fetch::input_path(day);
+
+    match fetch::fetch_input(&Site::from_env(), day, &path) {
+        Ok(Fetched::Downloaded(path)) => {
+            println!("downloaded {}", path.display());
+            0
+        },
+        Ok(Fetched::Cached(path)) => {
+            println!("{} already exists, not downloading it again", path.display());
+            0
+        },
+        Err(e) => {
+            eprintln!("day {}: {}", day, e);
+            1
+        },
+    }
+}
+
+// Solve one part and submit the answer unless the history says it's wrong.
+fn run_submit(config: &Config, number: u32, part: Part) -> i32 {
+    let Some(day) = days::find(number) else {
+        eprintln!("no solver for day {}", number);
+        return 1;
+    };
+    let day_config = config.day(number);
+    let answer = match aoc::run_day(day, &[part], &day.input(&day_config), &day_config.params).remove(0).answer {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("day {} part {}: {}", number, part, e);
+            return 1;
+        }
+    };
+
+    let mut history = match History::load(&submit::default_history_path()) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    match history.advise(number, part, &answer) {
+        Advice::Submit => {},
+        Advice::Warn(warning) => eprintln!("warning: {}", warning),
+        Advice::Refuse(reason) => {
+            eprintln!("not submitting: {}", reason);
+            return 1;
+        }
+    }
+
+    println!("day {} part {}: submitting {}", number, part, answer);
+    let verdict = match submit::submit(&Site::from_env(), number, part, &answer) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            eprintln!("day {} part {}: {}", number, part, e);
+            return 1;
+        }
+    };
+    println!("{}", verdict);
+
+    if let Err(e) = history.record(number, part, &answer, verdict.clone()) {
+        eprintln!("{}", e);
+    }
+
+    if matches!(verdict, Verdict::Correct | Verdict::AlreadySolved) { 0 } else { 1 }
+}
+
+fn run_watch(config: &Config, number: u32) -> i32 {
+    let Some(day) = days::find(number) else {
+        eprintln!("no solver for day {}", number);
+        return 1;
+    };
+
+    match watch::watch(day, &config.day(number)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        },
+    }
+}
+
+// Save a puzzle page's examples to the day's directory and register their
+// answers, returning the exit code.
+fn run_examples(number: u32, page: &Path) -> i32 {
+    let html = match fs::read_to_string(page) {
+        Ok(html) => html,
+        Err(e) => {
+            eprintln!("couldn't read {}: {}", page.display(), e);
+            return 1;
+        }
+    };
+    let dir = days::workspace_root().join(format!("day{}", number));
+    if !dir.is_dir() {
+        eprintln!("{} doesn't exist, create it with `aoc new {}` first", dir.display(), number);
+        return 1;
+    }
+
+    // With a solver, only blocks it can parse are examples. Without one yet,
+    // every block that has an answer is taken.
+    let extracted = match days::find(number) {
+        Some(day) => {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(|_| {}));
+            let extracted = examples::extract(&html, |text| {
+                verify::catch_panic(|| (day.parse)(text).is_ok()).unwrap_or(false)
+            });
+            panic::set_hook(hook);
+            extracted
+        },
+        None => examples::extract(&html, |_| true),
+    };
+    if extracted.examples.is_empty() {
+        eprintln!("no examples with answers found in {}", page.display());
+        return 1;
+    }
+
+    match examples::save(&extracted, number, &dir, &answers::default_path()) {
+        Ok(changes) => {
+            for change in changes {
+                println!("{}", change);
+            }
+            0
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        },
+    }
+}
+
+// Run a day with recording on, then save the frames as a cast or page
+// through them, returning the exit code.
+fn run_record(config: &Config, number: u32, part: Option<Part>, input: Option<Source>, cast: Option<PathBuf>, delay: Duration) -> i32 {
+    let Some(day) = days::find(number) else {
+        eprintln!("no solver for day {}", number);
+        return 1;
+    };
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let day_config = config.day(number);
+    let source = input.unwrap_or_else(|| day.input(&day_config));
+
+    frames::start();
+    let rows = aoc::run_day(day, &parts, &source, &day_config.params);
+    let recording = frames::finish();
+    report::print(&rows, report::Format::Text);
+
+    let result = match cast {
+        Some(path) => fs::write(&path, recording.to_cast(&format!("day {}", number), delay)).
+            map(|()| println!("recorded {} frames to {}", recording.frames.len(), path.display())).
+            map_err(|e| format!("couldn't write {}: {}", path.display(), e)),
+        None => frames::replay(&recording, io::stdin().lock(), io::stdout().lock()).map_err(|e| e.to_string()),
+    };
+
+    match result {
+        Ok(()) if rows.iter().all(|row| row.answer.is_ok()) => 0,
+        Ok(()) => 1,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        },
+    }
+}
+
+fn run_replay(cast: &Path) -> i32 {
+    let recording = match fs::read_to_string(cast).
+        map_err(|e| e.to_string()).
+        and_then(|text| Recording::from_cast(&text)) {
+        Ok(recording) => recording,
+        Err(e) => {
+            eprintln!("couldn't read {}: {}", cast.display(), e);
+            return 1;
+        }
+    };
+
+    match frames::replay(&recording, io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        },
+    }
+}
+
+fn run_new(day: u32) -> i32 {
+    match scaffold::new_day(days::workspace_root(), day) {
+        Ok(written) => {
+            for path in written {
+                println!("created {}", path.display());
+            }
+            println!();
+            println!("To run it with aoc, add it to aoc/Cargo.toml and the DAYS list in aoc/src/days.rs:");
+            println!("    day{} = {{ path = \"../day{}\" }}", day, day);
+            println!("    day!({}, day{}),", day, day);
+            0
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        },
+    }
+}
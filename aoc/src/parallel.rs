@@ -0,0 +1,215 @@
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::config::{Config, Params};
+use common::input::Source;
+use common::logger;
+
+use crate::days::{self, Day, Part};
+use crate::report::{self, format_duration, Format, Row};
+use crate::verify::catch_panic;
+
+// One part of one day, solved on whichever thread is free. Each job parses
+// its own copy of the input, so the two parts of a day can run side by side.
+pub struct Job {
+    pub day: &'static Day,
+    pub part: Part,
+    pub source: Source,
+    pub params: Params,
+}
+
+// Both parts of every day on their inputs and with their parameters from
+// config, in order.
+pub fn all_jobs(config: &Config) -> Vec<Job> {
+    let mut jobs = Vec::new();
+    for day in days::DAYS.iter() {
+        let day_config = config.day(day.number);
+        for part in Part::BOTH {
+            jobs.push(Job { day, part, source: day.input(&day_config), params: day_config.params.clone() });
+        }
+    }
+    jobs
+}
+
+pub struct Done {
+    pub row: Row,
+    // what the solver logged and the parse diagnostic, if any
+    pub output: String,
+    // how long the job took, parsing and solving
+    pub busy: Duration,
+    // the CPU time its thread used meanwhile, or busy where that can't be
+    // measured
+    pub cpu: Duration,
+}
+
+pub struct Summary {
+    // in the same order as the jobs
+    pub done: Vec<Done>,
+    pub threads: usize,
+    pub wall: Duration,
+}
+
+impl Summary {
+    // The CPU time of all the jobs, added up.
+    pub fn cpu(&self) -> Duration {
+        self.done.iter().map(|done| done.cpu).sum()
+    }
+}
+
+// The number of threads to use when none is given.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// Run the jobs on a pool of threads, each taking the next job that hasn't
+// been started until there are none left. A job that panics fails on its
+// own without stopping the others.
+pub fn run_jobs(jobs: &[Job], threads: usize) -> Summary {
+    let threads = threads.clamp(1, jobs.len().max(1));
+    let next = AtomicUsize::new(0);
+    let finished: Mutex<Vec<Option<Done>>> = Mutex::new(jobs.iter().map(|_| None).collect());
+
+    // panics are reported with the answers, so the default hook would only
+    // scatter the same messages across stderr
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+                let done = run_job(job);
+                finished.lock().unwrap()[i] = Some(done);
+            });
+        }
+    });
+    let wall = start.elapsed();
+
+    panic::set_hook(hook);
+
+    let done = finished.into_inner().unwrap().into_iter().map(Option::unwrap).collect();
+    Summary { done, threads, wall }
+}
+
+// The CPU time used so far by the calling thread, if the platform says.
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: time is a valid timespec for the call to fill in
+    let ok = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) } == 0;
+    ok.then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+fn run_job(job: &Job) -> Done {
+    let start = Instant::now();
+    let cpu_start = thread_cpu_time();
+    let (result, mut output) = logger::capture(|| catch_panic(|| crate::solve_day(job.day, &[job.part], &job.source, &job.params)));
+    let busy = start.elapsed();
+    let cpu = cpu_start.zip(thread_cpu_time()).map_or(busy, |(start, end)| end.saturating_sub(start));
+
+    let row = match result {
+        Ok((mut rows, diagnostic)) => {
+            output.extend(diagnostic);
+            rows.remove(0)
+        },
+        Err(e) => Row {
+            day: job.day.number,
+            part: job.part,
+            answer: Err(e),
+            input: job.source.clone(),
+            input_sha256: None,
+            parse_time: None,
+            time: busy,
+        },
+    };
+
+    Done { row, output, busy, cpu }
+}
+
+// Print each job's captured output to stderr, then the results in order and
+// how much running in parallel saved. In the JSON formats only the rows go
+// to stdout.
+pub fn print(summary: &Summary, format: Format) {
+    for done in summary.done.iter().filter(|done| !done.output.is_empty()) {
+        eprintln!("--- day {} part {} ---", done.row.day, done.row.part);
+        eprint!("{}", done.output);
+    }
+
+    let totals = format!("{} jobs on {} thread{}: {} wall time, {} CPU time ({:.2}x)",
+                         summary.done.len(), summary.threads, if summary.threads == 1 { "" } else { "s" },
+                         format_duration(summary.wall), format_duration(summary.cpu()),
+                         summary.cpu().as_secs_f64() / summary.wall.as_secs_f64().max(1e-9));
+
+    if format != Format::Text {
+        let rows: Vec<Row> = summary.done.iter().map(|done| done.row.clone()).collect();
+        report::print(&rows, format);
+        eprintln!("{}", totals);
+        return;
+    }
+
+    let width = summary.done.iter().
+        map(|done| match &done.row.answer { Ok(a) => a.len(), Err(e) => e.len() }).
+        max().unwrap_or(0).
+        max("Answer".len());
+
+    println!("Day | Part | Status | {:<width$} | Time", "Answer");
+    println!("----+------+--------+-{}-+-----------", "-".repeat(width));
+
+    for done in &summary.done {
+        let (status, answer) = match &done.row.answer {
+            Ok(answer) => ("ok", answer),
+            Err(e) => ("error", e),
+        };
+        println!("{:>3} | {:>4} | {:<6} | {:<width$} | {}", done.row.day, done.row.part, status, answer, format_duration(done.busy));
+    }
+
+    println!();
+    println!("{}", totals);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_jobs() {
+        let example = |number, part, name| {
+            let day = days::find(number).unwrap();
+            Job { day, part, source: Source::in_dir(day.dir(), name), params: Params::default() }
+        };
+        let mut jobs = vec![
+            example(9, Part::One, "input_test.txt"),
+            example(9, Part::Two, "input_test.txt"),
+            example(6, Part::One, "input_test.txt"),
+            example(6, Part::Two, "missing.txt"),
+            Job { day: days::find(6).unwrap(), part: Part::One, source: Source::text("bad", "Time: 7\n"), params: Params::default() },
+        ];
+
+        let params = Config::parse("[day11]\nexpansion = 10\n").unwrap().day(11).params;
+        jobs.push(Job { params: params.clone(), ..example(11, Part::Two, "input_test.txt") });
+        jobs.push(Job { params, ..example(9, Part::One, "input_test.txt") });
+
+        let summary = run_jobs(&jobs, 3);
+        let answers: Vec<_> = summary.done.iter().map(|done| (done.row.day, done.row.part, done.row.answer.is_ok())).collect();
+        assert_eq!(answers, [(9, Part::One, true), (9, Part::Two, true), (6, Part::One, true), (6, Part::Two, false), (6, Part::One, false), (11, Part::Two, true), (9, Part::One, false)]);
+        assert_eq!(summary.done[0].row.answer, Ok("114".to_string()));
+        assert_eq!(summary.done[1].row.answer, Ok("2".to_string()));
+        assert_eq!(summary.done[2].row.answer, Ok("288".to_string()));
+        assert!(summary.done[4].output.contains("= in <bad>"));
+        assert_eq!(summary.done[5].row.answer, Ok("1030".to_string()));
+        assert_eq!(summary.done[6].row.answer, Err("unknown parameter 'expansion', this day has none".to_string()));
+        assert_eq!(summary.threads, 3);
+    }
+}
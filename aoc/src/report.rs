@@ -0,0 +1,157 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use common::input::Source;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::days::{self, Part};
+
+// One line of the results table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<String, String>,
+    pub input: Source,
+    // None if the input couldn't be read
+    pub input_sha256: Option<String>,
+    // None if the input couldn't be parsed
+    pub parse_time: Option<Duration>,
+    // how long solving the part took, not counting parsing
+    pub time: Duration,
+}
+
+// How to print the rows: a table for people, or for scripts a JSON array or
+// JSON Lines (one object per row).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    JsonLines,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::JsonLines),
+            _ => Err(format!("invalid format '{}', expected text, json or jsonl", s)),
+        }
+    }
+}
+
+// A row as written in the JSON formats. Exactly one of answer and error is
+// set, and times are in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub input: String,
+    pub input_sha256: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+}
+
+impl Record {
+    pub fn new(row: &Row) -> Self {
+        Record {
+            day: row.day,
+            part: match row.part { Part::One => 1, Part::Two => 2 },
+            answer: row.answer.clone().ok(),
+            error: row.answer.clone().err(),
+            input: display_source(&row.input),
+            input_sha256: row.input_sha256.clone(),
+            parse_ns: row.parse_time.map(|t| t.as_nanos() as u64),
+            solve_ns: row.answer.is_ok().then_some(row.time.as_nanos() as u64),
+        }
+    }
+}
+
+// Paths inside the workspace are shown relative to it, so the output is the
+// same on every machine.
+fn display_source(source: &Source) -> String {
+    match source.path() {
+        Some(path) => path.strip_prefix(days::workspace_root()).unwrap_or(path).display().to_string(),
+        None => source.to_string(),
+    }
+}
+
+pub fn sha256(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn print(rows: &[Row], format: Format) {
+    let records = || rows.iter().map(Record::new);
+
+    match format {
+        Format::Text => print_table(rows),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&records().collect::<Vec<_>>()).unwrap()),
+        Format::JsonLines => {
+            for record in records() {
+                println!("{}", serde_json::to_string(&record).unwrap());
+            }
+        },
+    }
+}
+
+// Format a duration with a unit that keeps it short, e.g. "812ns",
+// "15.30µs", "4.07ms" or "1.25s".
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos() as f64;
+
+    if nanos < 1e3 {
+        format!("{}ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.2}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+pub fn print_table(rows: &[Row]) {
+    let width = rows.iter().
+        map(|row| match &row.answer { Ok(a) => a.len(), Err(e) => e.len() + 7 }).
+        max().unwrap_or(0).
+        max("Answer".len());
+
+    println!("Day | Part | {:<width$} | Time", "Answer");
+    println!("----+------+-{}-+-----------", "-".repeat(width));
+
+    for row in rows {
+        match &row.answer {
+            Ok(answer) => println!("{:>3} | {:>4} | {:<width$} | {}", row.day, row.part, answer, format_duration(row.time)),
+            Err(e) => println!("{:>3} | {:>4} | error: {}", row.day, row.part, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let row = Row {
+            day: 9,
+            part: Part::Two,
+            answer: Ok("2".to_string()),
+            input: Source::in_dir(days::workspace_root(), "day9/input_test.txt"),
+            input_sha256: Some(sha256("abc")),
+            parse_time: Some(Duration::from_micros(3)),
+            time: Duration::from_nanos(250),
+        };
+
+        assert_eq!(serde_json::to_string(&Record::new(&row)).unwrap(),
+                   concat!(r#"{"day":9,"part":2,"answer":"2","error":null,"input":"day9/input_test.txt","#,
+                           r#""input_sha256":"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad","#,
+                           r#""parse_ns":3000,"solve_ns":250}"#));
+    }
+}
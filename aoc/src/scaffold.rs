@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The files of a new day crate, relative to its directory, with @DAY@
+// standing for the day's number.
+// There's no input.txt, so that aoc fetch will download it.
+const FILES: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    (".gitignore", "/target\n"),
+    // paste the puzzle's example here
+    ("input_test.txt", ""),
+];
+
+// Create the crate for a day in root (the workspace), where the "day*"
+// members glob picks it up. Returns the files written. Refuses to touch a
+// day that already exists.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let mut written = Vec::new();
+    for (name, template) in FILES {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("couldn't create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, template.replace("@DAY@", &day.to_string())).
+            map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_day() {
+        let root = tempfile::tempdir().unwrap();
+        let written = new_day(root.path(), 12).unwrap();
+        assert_eq!(written.len(), FILES.len());
+
+        let lib = fs::read_to_string(root.path().join("day12/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub const DAY: u32 = 12;"));
+        let main = fs::read_to_string(root.path().join("day12/src/main.rs")).unwrap();
+        assert!(main.contains("day12::parse, day12::part1, day12::part2"));
+        assert!(!main.contains("@DAY@") && !lib.contains("@DAY@"));
+
+        assert!(new_day(root.path(), 12).is_err());
+    }
+}
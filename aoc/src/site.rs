@@ -0,0 +1,108 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ureq::Agent;
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2023;
+
+// The site asks automated tools to identify themselves, so say what we are
+// and how we behave.
+pub const USER_AGENT: &str = concat!(
+    "aoc-runner/", env!("CARGO_PKG_VERSION"),
+    " (personal Advent of Code 2023 solutions; each input is downloaded once and cached)"
+);
+
+// A response from the site, whatever its status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// Talks to adventofcode.com, or whatever base_url points to (e.g. a stub
+// server in the tests), logged in with the session token if there is one.
+pub struct Site {
+    pub base_url: String,
+    pub token: Option<String>,
+    agent: Agent,
+}
+
+impl Site {
+    pub fn new(base_url: &str, token: Option<String>) -> Self {
+        let agent: Agent = Agent::config_builder().
+            user_agent(USER_AGENT).
+            http_status_as_error(false).
+            timeout_global(Some(Duration::from_secs(30))).
+            build().
+            into();
+
+        Site { base_url: base_url.trim_end_matches('/').to_string(), token, agent }
+    }
+
+    // The real site (or $AOC_URL) with the token from find_token().
+    pub fn from_env() -> Self {
+        let base_url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Site::new(&base_url, find_token())
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}{}", self.base_url, YEAR, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.token.as_deref().unwrap_or_default())
+    }
+
+    // GET a path below the year, e.g. "/day/5/input".
+    pub fn get(&self, path: &str) -> Result<Response, String> {
+        let response = self.agent.get(self.url(path)).
+            header("Cookie", self.cookie()).
+            call();
+        read_response(response)
+    }
+
+    // POST a form to a path below the year, e.g. "/day/5/answer".
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+        let response = self.agent.post(self.url(path)).
+            header("Cookie", self.cookie()).
+            send_form(form.iter().copied());
+        read_response(response)
+    }
+}
+
+fn read_response(response: Result<ureq::http::Response<ureq::Body>, ureq::Error>) -> Result<Response, String> {
+    let mut response = response.map_err(|e| e.to_string())?;
+    Ok(Response {
+        status: response.status().as_u16(),
+        body: response.body_mut().read_to_string().map_err(|e| e.to_string())?,
+    })
+}
+
+// Where the runner's own settings live: $AOC_CONFIG_DIR, or "aoc" in the
+// usual per-user config directory.
+pub fn config_dir() -> PathBuf {
+    if let Ok(dir) = env::var("AOC_CONFIG_DIR") {
+        return PathBuf::from(dir);
+    }
+    match env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("aoc"),
+        _ => PathBuf::from(env::var("HOME").unwrap_or_default()).join(".config/aoc"),
+    }
+}
+
+pub fn token_path() -> PathBuf {
+    config_dir().join("session")
+}
+
+// The session cookie, from $AOC_SESSION or else the "session" file in the
+// config directory.
+pub fn find_token() -> Option<String> {
+    let token = env::var("AOC_SESSION").ok().
+        or_else(|| fs::read_to_string(token_path()).ok())?;
+    let token = token.trim();
+
+    (!token.is_empty()).then(|| token.to_string())
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::days::{self, Part};
+use crate::site::{self, Site};
+
+// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // wrong, without a hint which way
+    Wrong,
+    // we submitted too recently and have to wait (if the page said how long)
+    RateLimited(Option<Duration>),
+    // the part was already solved, so the answer wasn't checked
+    AlreadySolved,
+    // a page we don't recognise, with its text
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(Some(wait)) => write!(f, "rate limited, try again in {}s", wait.as_secs()),
+            Verdict::RateLimited(None) => write!(f, "rate limited, try again later"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unknown(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+// Work out the verdict from the HTML page returned after submitting.
+pub fn parse_response(html: &str) -> Verdict {
+    if html.contains("That's the right answer") {
+        Verdict::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(html))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(page_text(html))
+    }
+}
+
+// The time in "You have 1m 5s left to wait".
+fn parse_wait(html: &str) -> Option<Duration> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+
+    let mut secs = 0;
+    for amount in html[start..end].split_whitespace() {
+        let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        secs += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+// The text of the page's <article>, or the whole page, without tags.
+fn page_text(html: &str) -> String {
+    let article = html.split_once("<article>").
+        and_then(|(_, rest)| rest.split_once("</article>")).
+        map_or(html, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Post an answer to the site.
+pub fn submit(site: &Site, day: u32, part: Part, answer: &str) -> Result<Verdict, String> {
+    if site.token.is_none() {
+        return Err(format!("no session token, set AOC_SESSION or put it in {}", site::token_path().display()));
+    }
+
+    let level = part.to_string();
+    let response = site.post_form(&format!("/day/{}/answer", day), &[("level", &level), ("answer", answer)])?;
+
+    match response.status {
+        200 => Ok(parse_response(&response.body)),
+        400 | 401 | 403 => Err("the session token was rejected, it has probably expired".to_string()),
+        404 => Err(format!("day {} isn't unlocked yet", day)),
+        status => Err(format!("unexpected response {}: {}", status, page_text(&response.body))),
+    }
+}
+
+// One answer we've submitted before and what the site said about it. Only
+// final verdicts are kept, so there's nothing for rate limits and the like.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+// What to do with an answer before submitting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Advice {
+    Submit,
+    // worth a try, but surely wrong given what we already know
+    Warn(String),
+    // known not to be right, so don't waste a submission (and a timeout)
+    Refuse(String),
+}
+
+// Local record of every submission, one per line as
+//
+//   <day> <part> <answer> <correct|high|low|wrong>
+pub struct History {
+    pub path: PathBuf,
+    pub entries: Vec<Submission>,
+}
+
+pub fn default_history_path() -> PathBuf {
+    days::workspace_root().join("submissions.txt")
+}
+
+impl History {
+    // Load the history at path, which is fine not to exist yet.
+    pub fn load(path: &Path) -> Result<History, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+
+        let mut entries = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parsed = match fields[..] {
+                [day, part, answer, verdict] => day.parse::<u32>().ok().
+                    zip(part.parse::<Part>().ok()).
+                    zip(verdict_from_str(verdict)).
+                    map(|((day, part), verdict)| Submission { day, part, answer: answer.to_string(), verdict }),
+                _ => None,
+            };
+            match parsed {
+                Some(submission) => entries.push(submission),
+                None => return Err(format!("{}: line {}: expected <day> <part> <answer> <correct|high|low|wrong>", path.display(), i + 1)),
+            }
+        }
+
+        Ok(History { path: path.to_path_buf(), entries })
+    }
+
+    // Decide whether answer is worth submitting.
+    pub fn advise(&self, day: u32, part: Part, answer: &str) -> Advice {
+        let previous: Vec<&Submission> = self.entries.iter().
+            filter(|s| s.day == day && s.part == part).
+            collect();
+
+        if let Some(correct) = previous.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Advice::Refuse(format!("already solved, the answer was {}", correct.answer));
+        }
+        if let Some(wrong) = previous.iter().find(|s| s.answer == answer) {
+            return Advice::Refuse(format!("{} was already submitted and was {}", answer, wrong.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i64>() else {
+            return Advice::Submit;
+        };
+        let bound = |verdict: Verdict| previous.iter().
+            filter(move |s| s.verdict == verdict).
+            filter_map(|s| s.answer.parse::<i64>().ok());
+
+        if let Some(high) = bound(Verdict::TooHigh).min().filter(|&high| value >= high) {
+            return Advice::Warn(format!("{} is at least {}, which was too high", value, high));
+        }
+        if let Some(low) = bound(Verdict::TooLow).max().filter(|&low| value <= low) {
+            return Advice::Warn(format!("{} is at most {}, which was too low", value, low));
+        }
+
+        Advice::Submit
+    }
+
+    // Remember a submission, adding it to the file if the verdict is final.
+    pub fn record(&mut self, day: u32, part: Part, answer: &str, verdict: Verdict) -> Result<(), String> {
+        let Some(name) = verdict_name(&verdict) else {
+            return Ok(());
+        };
+
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&self.path).
+            map_err(|e| format!("couldn't open {}: {}", self.path.display(), e))?;
+        writeln!(file, "{} {} {} {}", day, part, answer, name).
+            map_err(|e| format!("couldn't write {}: {}", self.path.display(), e))?;
+
+        self.entries.push(Submission { day, part, answer: answer.to_string(), verdict });
+        Ok(())
+    }
+}
+
+fn verdict_name(verdict: &Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Correct => Some("correct"),
+        Verdict::TooHigh => Some("high"),
+        Verdict::TooLow => Some("low"),
+        Verdict::Wrong => Some("wrong"),
+        _ => None,
+    }
+}
+
+fn verdict_from_str(s: &str) -> Option<Verdict> {
+    match s {
+        "correct" => Some(Verdict::Correct),
+        "high" => Some(Verdict::TooHigh),
+        "low" => Some(Verdict::TooLow),
+        "wrong" => Some(Verdict::Wrong),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response("<article><p>That's the right answer!  You are one gold star closer.</p></article>"), Verdict::Correct);
+        assert_eq!(parse_response("<article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article>"), Verdict::TooHigh);
+        assert_eq!(parse_response("<article><p>That's not the right answer; your answer is too low.</p></article>"), Verdict::TooLow);
+        assert_eq!(parse_response("<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>"), Verdict::Wrong);
+        assert_eq!(parse_response("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>"),
+                   Verdict::RateLimited(Some(Duration::from_secs(65))));
+        assert_eq!(parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"), Verdict::AlreadySolved);
+        assert_eq!(parse_response("<html><article><p>Something <em>new</em>.</p></article></html>"), Verdict::Unknown("Something new.".to_string()));
+    }
+
+    #[test]
+    fn test_advise() {
+        let submission = |answer: &str, verdict| Submission { day: 5, part: Part::One, answer: answer.to_string(), verdict };
+        let mut history = History {
+            path: PathBuf::new(),
+            entries: vec![submission("500", Verdict::TooHigh), submission("100", Verdict::TooLow), submission("300", Verdict::Wrong)],
+        };
+
+        assert!(matches!(history.advise(5, Part::One, "300"), Advice::Refuse(_)));
+        assert!(matches!(history.advise(5, Part::One, "600"), Advice::Warn(_)));
+        assert!(matches!(history.advise(5, Part::One, "50"), Advice::Warn(_)));
+        assert_eq!(history.advise(5, Part::One, "250"), Advice::Submit);
+        assert_eq!(history.advise(5, Part::Two, "300"), Advice::Submit);
+
+        history.entries.push(submission("250", Verdict::Correct));
+        assert!(matches!(history.advise(5, Part::One, "251"), Advice::Refuse(_)));
+    }
+}
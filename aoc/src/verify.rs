@@ -0,0 +1,109 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use common::input::Source;
+
+use crate::answers::Answers;
+use crate::days::{Day, Part};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    // no expected answer is registered, so the solver wasn't run
+    Missing,
+    // the part doesn't apply to this input
+    NotApplicable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u32,
+    pub part: Part,
+    pub input: String,
+    pub status: Status,
+}
+
+// Run a solver, turning a panic into an error message.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(msg) = payload.downcast_ref::<&str>() {
+            format!("panicked: {}", msg)
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            format!("panicked: {}", msg)
+        } else {
+            "panicked".to_string()
+        }
+    })
+}
+
+// Check both parts of a day against every input that is either on disk or
+// mentioned in the registry.
+pub fn verify_day(day: &Day, answers: &Answers) -> Vec<Check> {
+    let mut inputs = day.inputs();
+    for expected in answers.entries.iter().filter(|e| e.day == day.number) {
+        if !inputs.contains(&expected.input) {
+            inputs.push(expected.input.clone());
+        }
+    }
+
+    inputs.iter().flat_map(|input| verify_input(day, answers, input)).collect()
+}
+
+// Check both parts of a day against one input file in its directory.
+pub fn verify_input(day: &Day, answers: &Answers, input: &str) -> Vec<Check> {
+    // only read and parse the input if some part needs it
+    let mut solved = None;
+    let mut checks = Vec::new();
+
+    for part in Part::BOTH {
+        let status = match answers.get(day.number, part, input) {
+            None => Status::Missing,
+            Some(expected) => match &expected.answer {
+                None => Status::NotApplicable,
+                Some(answer) => {
+                    let solved = solved.get_or_insert_with(|| {
+                        let text = Source::in_dir(day.dir(), input).read()?;
+                        (day.parse)(&text).map_err(|e| e.to_string())
+                    });
+
+                    match solved {
+                        Err(e) => Status::Fail(e.clone()),
+                        Ok(solved) => match catch_panic(|| solved.solve(part)).and_then(|answer| answer) {
+                            Err(e) => Status::Fail(e),
+                            Ok(actual) if actual == *answer => Status::Pass,
+                            Ok(actual) => Status::Fail(format!("got {}, expected {}", actual, answer)),
+                        },
+                    }
+                },
+            },
+        };
+
+        checks.push(Check { day: day.number, part, input: input.to_string(), status });
+    }
+
+    checks
+}
+
+pub fn print_report(checks: &[Check]) {
+    let width = checks.iter().map(|c| c.input.len()).max().unwrap_or(0).max("Input".len());
+
+    println!("Day | Part | {:<width$} | Status", "Input");
+    println!("----+------+-{}-+-------", "-".repeat(width));
+
+    for check in checks {
+        let status = match &check.status {
+            Status::Pass => "pass".to_string(),
+            Status::Fail(e) => format!("FAIL: {}", e),
+            Status::Missing => "missing".to_string(),
+            Status::NotApplicable => "n/a".to_string(),
+        };
+        println!("{:>3} | {:>4} | {:<width$} | {}", check.day, check.part, check.input, status);
+    }
+
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    println!();
+    println!("{} passed, {} failed, {} missing",
+             count(|s| *s == Status::Pass),
+             count(|s| matches!(s, Status::Fail(_))),
+             count(|s| *s == Status::Missing));
+}
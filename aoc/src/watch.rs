@@ -0,0 +1,264 @@
+use std::io::{self, Write};
+use std::panic;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use log::warn;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use common::config::{DayConfig, Params};
+use common::input::Source;
+
+use crate::answers::{self, Answers};
+use crate::days::{self, Day, Part};
+use crate::report::format_duration;
+use crate::verify::catch_panic;
+
+// How long the inputs have to stay unchanged before solving again, so an
+// editor saving in several steps only causes one redraw.
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+// The input set in aoc.toml, if it's somewhere the day's directory listing
+// won't find it: another directory, or a name that isn't input*.txt. Its
+// directory is made canonical so it compares equal to the watcher's paths.
+pub fn configured_input(dir: &Path, config: &DayConfig) -> Option<PathBuf> {
+    let path = config.input.as_ref()?;
+    let name = path.file_name()?;
+    let parent = fs::canonicalize(path.parent().unwrap_or(Path::new("."))).ok()?;
+    if fs::canonicalize(dir).is_ok_and(|dir| dir == parent) && name.to_str().is_some_and(days::is_input_name) {
+        return None;
+    }
+    Some(parent.join(name))
+}
+
+// Whether the event changed one of the day's inputs, or the configured one.
+fn is_input_change(event: &Event, configured: Option<&Path>) -> bool {
+    !matches!(event.kind, EventKind::Access(_)) && event.paths.iter().any(|path| {
+        Some(path.as_path()) == configured || path.file_name().
+            and_then(|name| name.to_str()).
+            is_some_and(days::is_input_name)
+    })
+}
+
+// Block until an input changes and nothing else happens for quiet. Returns
+// false once the watcher has gone away.
+pub fn wait_for_change(events: &Receiver<notify::Result<Event>>, configured: Option<&Path>, quiet: Duration) -> bool {
+    loop {
+        match events.recv() {
+            Ok(Ok(event)) if is_input_change(&event, configured) => break,
+            Ok(Ok(_)) => {},
+            Ok(Err(e)) => warn!("watch error: {}", e),
+            Err(_) => return false,
+        }
+    }
+
+    loop {
+        match events.recv_timeout(quiet) {
+            Ok(_) => {},
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return true,
+        }
+    }
+}
+
+// The answers and timings of both parts on every input in dir, and on the
+// configured input if there is one, solved with params. Read and parse
+// errors, bad parameters, and solvers that panic, are shown in place of the
+// answers.
+pub fn render(day: &Day, dir: &Path, configured: Option<&Path>, params: &Params, answers: &Answers) -> String {
+    let mut inputs = Vec::new();
+    for name in days::inputs_in(dir) {
+        inputs.push((name.clone(), Source::in_dir(dir, &name)));
+    }
+    if let Some(path) = configured {
+        inputs.push((path.display().to_string(), Source::Path(path.to_path_buf())));
+    }
+    if inputs.is_empty() {
+        return format!("no input*.txt files in {}\n", dir.display());
+    }
+
+    let mut lines = Vec::new();
+    for (name, source) in inputs {
+        let text = match source.read() {
+            Ok(text) => text,
+            Err(e) => {
+                lines.push(format!("{}: {}", name, e));
+                lines.push(String::new());
+                continue;
+            }
+        };
+
+        let start = Instant::now();
+        let mut solved = match catch_panic(|| (day.parse)(&text)) {
+            Ok(Ok(solved)) => solved,
+            Ok(Err(e)) => {
+                lines.push(format!("{}: parse error", name));
+                lines.push(e.render(&text));
+                lines.push(String::new());
+                continue;
+            },
+            Err(e) => {
+                lines.push(format!("{}: parse {}", name, e));
+                lines.push(String::new());
+                continue;
+            },
+        };
+        lines.push(format!("{} (parsed in {})", name, format_duration(start.elapsed())));
+        if let Err(e) = solved.configure(params) {
+            lines.push(format!("  {}", e));
+            lines.push(String::new());
+            continue;
+        }
+
+        for part in Part::BOTH {
+            let expected = answers.get(day.number, part, &name);
+            if expected.is_some_and(|e| e.answer.is_none()) {
+                lines.push(format!("  part {}  n/a", part));
+                continue;
+            }
+
+            let start = Instant::now();
+            let answer = catch_panic(|| solved.solve(part)).and_then(|answer| answer);
+            let time = format_duration(start.elapsed());
+            lines.push(match (answer, expected.and_then(|e| e.answer.as_ref())) {
+                (Ok(answer), Some(expected)) if answer != *expected =>
+                    format!("  part {}  {:<20} {:>10}  expected {}", part, answer, time, expected),
+                (Ok(answer), _) => format!("  part {}  {:<20} {:>10}", part, answer, time),
+                (Err(e), _) => format!("  part {}  {}", part, e),
+            });
+        }
+        lines.push(String::new());
+    }
+
+    lines.join("\n")
+}
+
+// Solve the day's inputs and redraw the results whenever one of them is
+// saved, until interrupted.
+pub fn watch(day: &Day, config: &DayConfig) -> Result<(), String> {
+    let dir = day.dir();
+    let configured = configured_input(&dir, config);
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).
+        map_err(|e| format!("couldn't watch {}: {}", dir.display(), e))?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive).
+        map_err(|e| format!("couldn't watch {}: {}", dir.display(), e))?;
+    // the configured input's directory rather than the file, as editors
+    // often save by replacing the file
+    if let Some(parent) = configured.as_deref().and_then(Path::parent) {
+        watcher.watch(parent, RecursiveMode::NonRecursive).
+            map_err(|e| format!("couldn't watch {}: {}", parent.display(), e))?;
+    }
+
+    // panics are shown along with the answers, so don't let the default
+    // hook write over the screen as well
+    panic::set_hook(Box::new(|_| {}));
+
+    loop {
+        // reloaded each time in case the examples' answers were filled in
+        let (answers, problem) = match Answers::load(&answers::default_path()) {
+            Ok(answers) => (answers, None),
+            Err(e) => (Answers::default(), Some(e)),
+        };
+
+        // clear the screen and go back to the top
+        print!("\x1b[2J\x1b[H");
+        match &configured {
+            Some(path) => println!("day {}, watching {} and {} (Ctrl-C to stop)", day.number, dir.display(), path.display()),
+            None => println!("day {}, watching {} (Ctrl-C to stop)", day.number, dir.display()),
+        }
+        println!();
+        if let Some(problem) = problem {
+            println!("{}", problem);
+            println!();
+        }
+        print!("{}", render(day, &dir, configured.as_deref(), &config.params, &answers));
+        io::stdout().flush().map_err(|e| e.to_string())?;
+
+        if !wait_for_change(&events, configured.as_deref(), DEBOUNCE) {
+            return Err("stopped receiving file changes".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use notify::event::{AccessKind, ModifyKind};
+
+    #[test]
+    fn test_render() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("input_test.txt"), "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n").unwrap();
+        fs::write(dir.path().join("input_test2.txt"), "0 3 six\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not an input").unwrap();
+        let answers = Answers::parse("9 1 input_test.txt 114\n9 2 input_test.txt 3\n").unwrap();
+
+        let view = render(days::find(9).unwrap(), dir.path(), None, &Params::default(), &answers);
+        let lines: Vec<&str> = view.lines().collect();
+        assert!(lines[0].starts_with("input_test.txt (parsed in "));
+        assert!(lines[1].starts_with("  part 1  114 "));
+        assert!(!lines[1].contains("expected"));
+        assert!(lines[2].starts_with("  part 2  2 ") && lines[2].ends_with("expected 3"));
+        assert_eq!(lines[4], "input_test2.txt: parse error");
+        assert!(view.contains("six"));
+        assert!(!view.contains("notes.txt"));
+    }
+
+    #[test]
+    fn test_wait_for_change() {
+        let event = |kind, path: &str| Ok(Event::new(kind).add_path(PathBuf::from(path)));
+
+        // a burst of writes to an input is one change
+        let (sender, events) = mpsc::channel();
+        for _ in 0..5 {
+            sender.send(event(EventKind::Modify(ModifyKind::Any), "day9/input.txt")).unwrap();
+        }
+        let later = sender.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            later.send(event(EventKind::Modify(ModifyKind::Any), "day9/input_test.txt")).unwrap();
+        });
+        assert!(wait_for_change(&events, None, Duration::from_millis(100)));
+        assert!(events.try_recv().is_err());
+
+        // other files, and reading an input, don't count
+        sender.send(event(EventKind::Modify(ModifyKind::Any), "day9/src/lib.rs")).unwrap();
+        sender.send(event(EventKind::Access(AccessKind::Any), "day9/input.txt")).unwrap();
+        drop(sender);
+        assert!(!wait_for_change(&events, None, Duration::from_millis(100)));
+
+        // nor does anything next to the configured input but the input itself
+        let (sender, events) = mpsc::channel();
+        sender.send(event(EventKind::Modify(ModifyKind::Any), "inputs/notes.txt")).unwrap();
+        sender.send(event(EventKind::Modify(ModifyKind::Any), "inputs/day9.txt")).unwrap();
+        drop(sender);
+        assert!(wait_for_change(&events, Some(Path::new("inputs/day9.txt")), Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_configured_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let day_dir = dir.path().join("day9");
+        let elsewhere = dir.path().join("inputs");
+        fs::create_dir(&day_dir).unwrap();
+        fs::create_dir(&elsewhere).unwrap();
+        fs::write(day_dir.join("input_test.txt"), "0 3 6 9 12 15\n").unwrap();
+        fs::write(elsewhere.join("day9.txt"), "1 3 6 10 15 21\n").unwrap();
+        let config = |path: PathBuf| DayConfig { input: Some(path), ..DayConfig::default() };
+
+        // one the directory listing already finds isn't watched twice
+        assert_eq!(configured_input(&day_dir, &DayConfig::default()), None);
+        assert_eq!(configured_input(&day_dir, &config(day_dir.join("input.txt"))), None);
+        assert_eq!(configured_input(&day_dir, &config(day_dir.join("real.txt"))),
+                   Some(fs::canonicalize(&day_dir).unwrap().join("real.txt")));
+
+        let configured = configured_input(&day_dir, &config(elsewhere.join("day9.txt"))).unwrap();
+        assert_eq!(configured, fs::canonicalize(&elsewhere).unwrap().join("day9.txt"));
+        let view = render(days::find(9).unwrap(), &day_dir, Some(&configured), &Params::default(), &Answers::default());
+        assert!(view.contains("input_test.txt (parsed in "));
+        assert!(view.contains(&format!("{} (parsed in ", configured.display())));
+    }
+}
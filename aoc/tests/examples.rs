@@ -0,0 +1,26 @@
+// Every example input (input_test*.txt) in every day's directory must have a
+// registered answer for both parts in answers.txt, and the solvers must agree
+// with it.
+use aoc::answers::{self, Answers};
+use aoc::days;
+use aoc::verify::{self, Status};
+
+#[test]
+fn test_examples() {
+    let answers = Answers::load(&answers::default_path()).unwrap();
+    let mut failures = Vec::new();
+
+    for day in days::DAYS {
+        for input in day.inputs().iter().filter(|input| input.starts_with("input_test")) {
+            for check in verify::verify_input(day, &answers, input) {
+                match check.status {
+                    Status::Pass | Status::NotApplicable => {},
+                    Status::Missing => failures.push(format!("day {} part {} {}: no answer registered", check.day, check.part, check.input)),
+                    Status::Fail(e) => failures.push(format!("day {} part {} {}: {}", check.day, check.part, check.input, e)),
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "example checks failed:\n{}", failures.join("\n"));
+}
@@ -0,0 +1,56 @@
+// aoc fetch against a stub server.
+mod stub;
+
+use std::fs;
+
+use aoc::fetch::{self, FetchError, Fetched};
+use aoc::site::{Site, USER_AGENT};
+
+#[test]
+fn test_download() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("day5/input.txt");
+    let (url, requests) = stub::serve_once(200, "seeds: 79 14 55 13\n");
+
+    let site = Site::new(&url, Some("abc123".to_string()));
+    assert_eq!(fetch::fetch_input(&site, 5, &dest), Ok(Fetched::Downloaded(dest.clone())));
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "seeds: 79 14 55 13\n");
+
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("GET /2023/day/5/input "), "{}", request);
+    assert!(request.contains("session=abc123"), "{}", request);
+    assert!(request.to_lowercase().contains(&format!("user-agent: {}", USER_AGENT.to_lowercase())), "{}", request);
+}
+
+#[test]
+fn test_cached() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("input.txt");
+    fs::write(&dest, "mine").unwrap();
+
+    // no token and no server: neither should be needed
+    let site = Site::new(&stub::unreachable(), None);
+    assert_eq!(fetch::fetch_input(&site, 5, &dest), Ok(Fetched::Cached(dest.clone())));
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "mine");
+}
+
+#[test]
+fn test_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("input.txt");
+
+    let site = Site::new(&stub::unreachable(), None);
+    assert_eq!(fetch::fetch_input(&site, 5, &dest), Err(FetchError::NoToken));
+
+    let (url, _) = stub::serve_once(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n");
+    let site = Site::new(&url, Some("expired".to_string()));
+    assert_eq!(fetch::fetch_input(&site, 5, &dest), Err(FetchError::Unauthenticated));
+
+    let (url, _) = stub::serve_once(404, "Please don't repeatedly request this endpoint before it unlocks!");
+    let site = Site::new(&url, Some("abc123".to_string()));
+    assert_eq!(fetch::fetch_input(&site, 25, &dest), Err(FetchError::NotUnlocked(25)));
+
+    // nothing is left behind to be taken for a cached input
+    assert!(!dest.exists());
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+}
@@ -0,0 +1,43 @@
+// aoc submit against a stub server.
+mod stub;
+
+use std::fs;
+
+use aoc::days::Part;
+use aoc::site::Site;
+use aoc::submit::{self, Advice, History, Verdict};
+
+#[test]
+fn test_submit() {
+    let (url, requests) = stub::serve_once(200, "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>");
+    let site = Site::new(&url, Some("abc123".to_string()));
+
+    assert_eq!(submit::submit(&site, 7, Part::Two, "252127335"), Ok(Verdict::TooHigh));
+
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("POST /2023/day/7/answer "), "{}", request);
+    assert!(request.contains("session=abc123"), "{}", request);
+    assert!(request.ends_with("level=2&answer=252127335"), "{}", request);
+
+    // nothing is sent without a token
+    let site = Site::new(&stub::unreachable(), None);
+    assert!(submit::submit(&site, 7, Part::Two, "252127335").is_err());
+}
+
+#[test]
+fn test_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("submissions.txt");
+
+    let mut history = History::load(&path).unwrap();
+    assert!(history.entries.is_empty());
+    history.record(7, Part::Two, "300", Verdict::TooHigh).unwrap();
+    history.record(7, Part::Two, "200", Verdict::RateLimited(None)).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "7 2 300 high\n");
+
+    // a fresh load remembers the wrong answer but not the rate limited one
+    let history = History::load(&path).unwrap();
+    assert!(matches!(history.advise(7, Part::Two, "300"), Advice::Refuse(_)));
+    assert!(matches!(history.advise(7, Part::Two, "301"), Advice::Warn(_)));
+    assert_eq!(history.advise(7, Part::Two, "200"), Advice::Submit);
+}
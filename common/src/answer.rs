@@ -0,0 +1,39 @@
+// What a part's solver gives back, made into text to show. Most solvers
+// return a number, but one that can find there is no answer (say a walk
+// that never gets where it's going) returns a Result with the reason.
+pub trait Solution {
+    fn answer(self) -> Result<String, String>;
+}
+
+macro_rules! number_solution {
+    ($($t:ty),*) => {
+        $(
+            impl Solution for $t {
+                fn answer(self) -> Result<String, String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+number_solution!(i32, i64, u32, u64, usize);
+
+impl<T: Solution> Solution for Result<T, String> {
+    fn answer(self) -> Result<String, String> {
+        self.and_then(Solution::answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(42u64.answer(), Ok("42".to_string()));
+        assert_eq!((-3i32).answer(), Ok("-3".to_string()));
+        assert_eq!(Ok::<usize, String>(6).answer(), Ok("6".to_string()));
+        assert_eq!(Err::<usize, String>("no way there".to_string()).answer(), Err("no way there".to_string()));
+    }
+}
@@ -0,0 +1,229 @@
+// The workspace's aoc.toml, which can point a day at a different input and
+// change the numbers in its puzzle, e.g.
+//
+//   [day2]
+//   input = "day2/variant.txt"
+//   max_red = 20
+//
+// Every key in a day's table other than input is one of its parameters.
+// Anything left out keeps the solver's default, which is the puzzle's.
+// Parameters whose names end in _file are paths, and like input are taken
+// from aoc.toml's directory.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::input::Source;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+// Named parameters for one day's solver.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: BTreeMap<String, toml::Value>,
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: toml::Value) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    // Check that every parameter is one of known, so a misspelt name isn't
+    // silently ignored.
+    pub fn only(&self, known: &[&str]) -> Result<(), String> {
+        match self.values.keys().find(|name| !known.contains(&name.as_str())) {
+            None => Ok(()),
+            Some(name) if known.is_empty() => Err(format!("unknown parameter '{}', this day has none", name)),
+            Some(name) => Err(format!("unknown parameter '{}', expected one of {}", name, known.join(", "))),
+        }
+    }
+
+    // An integer parameter, or default if it isn't set.
+    pub fn int<T: TryFrom<i64>>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.values.get(name) {
+            None => Ok(default),
+            Some(toml::Value::Integer(n)) => T::try_from(*n).map_err(|_| format!("{} = {} is out of range", name, n)),
+            Some(value) => Err(format!("{} should be an integer, not {}", name, value)),
+        }
+    }
+
+    // A string parameter, or default if it isn't set.
+    pub fn string(&self, name: &str, default: &str) -> Result<String, String> {
+        match self.values.get(name) {
+            None => Ok(default.to_string()),
+            Some(toml::Value::String(s)) => Ok(s.clone()),
+            Some(value) => Err(format!("{} should be a string, not {}", name, value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DayConfig {
+    pub input: Option<PathBuf>,
+    pub params: Params,
+}
+
+impl DayConfig {
+    // Where a day's binary reads its input: the first command line
+    // argument, or else the configured input, or else input.txt in the
+    // day's directory.
+    pub fn source(&self, manifest_dir: &str) -> Source {
+        Source::from_args_or(self.default_source(manifest_dir))
+    }
+
+    // The configured input, or else input.txt in the day's directory.
+    pub fn default_source(&self, manifest_dir: &str) -> Source {
+        match &self.input {
+            Some(path) => Source::Path(path.clone()),
+            None => Source::in_dir(manifest_dir, "input.txt"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub days: BTreeMap<u32, DayConfig>,
+}
+
+impl Config {
+    // Read the config at path, which is fine not to exist. Relative input
+    // and _file paths are taken from the directory it's in.
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+
+        let mut config = Config::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        config.resolve_paths(path.parent().unwrap_or(Path::new("")));
+        Ok(config)
+    }
+
+    // Join the relative paths in the config to dir.
+    fn resolve_paths(&mut self, dir: &Path) {
+        for day in self.days.values_mut() {
+            day.input = day.input.take().map(|input| dir.join(input));
+
+            for (name, value) in day.params.values.iter_mut() {
+                if let toml::Value::String(path) = value {
+                    if name.ends_with("_file") {
+                        *path = dir.join(&*path).to_string_lossy().into_owned();
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+
+        let mut config = Config::default();
+        for (key, value) in table {
+            let day = key.strip_prefix("day").
+                and_then(|day| day.parse::<u32>().ok()).
+                ok_or_else(|| format!("unexpected [{}], expected tables like [day2]", key))?;
+            let toml::Value::Table(entries) = value else {
+                return Err(format!("{} should be a table", key));
+            };
+
+            let day_config = config.days.entry(day).or_default();
+            for (name, value) in entries {
+                match (name.as_str(), value) {
+                    ("input", toml::Value::String(path)) => day_config.input = Some(PathBuf::from(path)),
+                    ("input", _) => return Err(format!("{}.input should be a path", key)),
+                    (_, value) => day_config.params.set(&name, value),
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    // The settings for a day, which are the defaults if it has none.
+    pub fn day(&self, day: u32) -> DayConfig {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    // Apply a setting from the command line, "day2.max_red=20", or just
+    // "max_red=20" for the one day being run. The value is read as TOML if
+    // it can be, and otherwise taken as a string.
+    pub fn set(&mut self, spec: &str, only_day: Option<u32>) -> Result<(), String> {
+        let (name, value) = spec.split_once('=').ok_or_else(|| format!("expected NAME=VALUE, not '{}'", spec))?;
+
+        let (day, name) = match name.split_once('.') {
+            Some((day, name)) => match day.strip_prefix("day").and_then(|day| day.parse::<u32>().ok()) {
+                Some(day) => (day, name),
+                None => return Err(format!("expected dayN.NAME, not '{}'", name)),
+            },
+            None => match only_day {
+                Some(day) => (day, name),
+                None => return Err(format!("say which day '{}' is for, e.g. day2.{}", name, name)),
+            },
+        };
+
+        let value = format!("value = {}", value).parse::<toml::Table>().
+            ok().
+            and_then(|mut table| table.remove("value")).
+            unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+        let day_config = self.days.entry(day).or_default();
+        if name == "input" {
+            day_config.input = Some(PathBuf::from(value.as_str().map_or_else(|| value.to_string(), str::to_string)));
+        } else {
+            day_config.params.set(name, value);
+        }
+        Ok(())
+    }
+}
+
+// The settings for one day from aoc.toml in the workspace, for the day's
+// own binary. manifest_dir is the day crate's directory.
+pub fn for_day(manifest_dir: &str, day: u32) -> Result<DayConfig, String> {
+    let path = Path::new(manifest_dir).join("..").join(FILE_NAME);
+    Config::load(&path).map(|config| config.day(day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config() {
+        let mut config = Config::parse("[day2]\nmax_red = 20\ninput = \"day2/variant.txt\"\n\n[day8]\nstart = \"BBB\"\n").unwrap();
+        let day2 = config.day(2);
+        assert_eq!(day2.input, Some(PathBuf::from("day2/variant.txt")));
+        assert_eq!(day2.params.int("max_red", 12), Ok(20));
+        assert_eq!(day2.params.int("max_blue", 14), Ok(14));
+        assert!(day2.params.string("max_red", "x").is_err());
+        assert!(day2.params.int::<u8>("max_red", 0).is_ok());
+        assert!(day2.params.only(&["max_red"]).is_ok());
+        assert!(day2.params.only(&["max_blue"]).is_err());
+        assert_eq!(config.day(8).params.string("start", "AAA"), Ok("BBB".to_string()));
+        assert_eq!(config.day(5), DayConfig::default());
+
+        config.set("day2.max_red=25", None).unwrap();
+        config.set("start=CCC", Some(8)).unwrap();
+        config.set("day11.expansion=10", None).unwrap();
+        assert_eq!(config.day(2).params.int("max_red", 12), Ok(25));
+        assert_eq!(config.day(8).params.string("start", "AAA"), Ok("CCC".to_string()));
+        assert_eq!(config.day(11).params.int("expansion", 0), Ok(10));
+
+        assert!(config.set("max_red=25", None).is_err());
+        assert!(config.set("max_red", Some(2)).is_err());
+        assert!(Config::parse("[part2]\nx = 1\n").is_err());
+        assert!(Config::parse("day2 = 5\n").is_err());
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let mut config = Config::parse("[day1]\ninput = \"day1/other.txt\"\nwords_file = \"words.txt\"\nwords = \"german\"\n\n[day2]\ninput = \"/tmp/day2.txt\"\n").unwrap();
+        config.resolve_paths(Path::new("/aoc"));
+
+        let day1 = config.day(1);
+        assert_eq!(day1.input, Some(PathBuf::from("/aoc/day1/other.txt")));
+        assert_eq!(day1.params.string("words_file", ""), Ok("/aoc/words.txt".to_string()));
+        assert_eq!(day1.params.string("words", ""), Ok("german".to_string()));
+        assert_eq!(config.day(2).input, Some(PathBuf::from("/tmp/day2.txt")));
+    }
+}
@@ -0,0 +1,24 @@
+// Differential testing: run a slow but obviously correct reference solver
+// and an optimized one on generated inputs and check that they agree. The
+// calling crate needs proptest as a dev-dependency.
+//
+//   differential!(test_name, strategy, reference_fn, optimized_fn);
+//
+// Both functions take a reference to the generated value. On a
+// disagreement proptest shrinks the input to a minimal reproducer, reports
+// it with both answers, and saves it under proptest-regressions/ so it is
+// tried first from then on.
+#[macro_export]
+macro_rules! differential {
+    ($name:ident, $strategy:expr, $reference:expr, $optimized:expr) => {
+        ::proptest::proptest! {
+            #[test]
+            fn $name(input in $strategy) {
+                let expected = ($reference)(&input);
+                let actual = ($optimized)(&input);
+                ::proptest::prop_assert_eq!(&actual, &expected,
+                    "optimized solver (left) disagrees with the reference (right)");
+            }
+        }
+    };
+}
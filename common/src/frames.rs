@@ -0,0 +1,194 @@
+// Recording what a solver does, to watch afterwards. Solvers call snapshot()
+// with a picture of their state, such as a grid, and status() with a line
+// saying what they're up to. Both do nothing unless a recording has been
+// started, and only build their text when one has, so they cost nothing in
+// a normal run.
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+// What was on the screen at one point: the latest snapshot, with a status
+// line below it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub screen: String,
+    pub status: String,
+}
+
+// Start recording, throwing away anything recorded before.
+pub fn start() {
+    FRAMES.lock().unwrap().clear();
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+// Stop recording and take the frames recorded since start().
+pub fn finish() -> Recording {
+    RECORDING.store(false, Ordering::Relaxed);
+    Recording { frames: std::mem::take(&mut *FRAMES.lock().unwrap()) }
+}
+
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+// Record a new picture, labelled with a status line.
+pub fn snapshot(label: &str, screen: impl FnOnce() -> String) {
+    if is_recording() {
+        FRAMES.lock().unwrap().push(Frame { screen: screen(), status: label.to_string() });
+    }
+}
+
+// Record a new status line under the latest picture.
+pub fn status(line: impl FnOnce() -> String) {
+    if is_recording() {
+        let mut frames = FRAMES.lock().unwrap();
+        let screen = frames.last().map(|frame| frame.screen.clone()).unwrap_or_default();
+        frames.push(Frame { screen, status: line() });
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub frames: Vec<Frame>,
+}
+
+impl Frame {
+    // The frame as terminal output: clear the screen, then draw it.
+    fn to_terminal(&self) -> String {
+        let mut out = "\x1b[H\x1b[2J".to_string();
+        for line in self.screen.lines() {
+            out.push_str(line);
+            out.push_str("\r\n");
+        }
+        out.push_str(&self.status);
+        out
+    }
+}
+
+impl Recording {
+    // The terminal size that fits every frame, as (columns, rows).
+    pub fn size(&self) -> (usize, usize) {
+        let lines = || self.frames.iter().flat_map(|frame| frame.screen.lines().chain([frame.status.as_str()]));
+        let width = lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let height = self.frames.iter().map(|frame| frame.screen.lines().count() + 1).max().unwrap_or(0);
+        (width.max(1), height.max(1))
+    }
+
+    // The recording as an asciicast v2 file, showing each frame for delay.
+    pub fn to_cast(&self, title: &str, delay: Duration) -> String {
+        let (width, height) = self.size();
+        let header = serde_json::json!({ "version": 2, "width": width, "height": height, "title": title });
+
+        let mut cast = header.to_string() + "\n";
+        for (i, frame) in self.frames.iter().enumerate() {
+            let time = delay.as_secs_f64() * i as f64;
+            cast += &serde_json::to_string(&(time, "o", frame.to_terminal())).unwrap();
+            cast.push('\n');
+        }
+        cast
+    }
+
+    // Read back an asciicast v2 file, taking each output event as a frame.
+    // The status line can't be told apart from the picture any more, so it
+    // stays part of the screen.
+    pub fn from_cast(cast: &str) -> Result<Recording, String> {
+        let mut lines = cast.lines().filter(|line| !line.trim().is_empty());
+        let header: serde_json::Value = lines.next().
+            ok_or("empty cast file")?.
+            parse().
+            map_err(|e| format!("invalid header: {}", e))?;
+        if header["version"] != 2 {
+            return Err("not an asciicast v2 file".to_string());
+        }
+
+        let mut frames = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let (_, kind, data): (f64, String, String) = serde_json::from_str(line).
+                map_err(|e| format!("event {}: {}", i + 1, e))?;
+            if kind == "o" {
+                let screen = data.trim_start_matches("\x1b[H\x1b[2J").replace("\r\n", "\n");
+                frames.push(Frame { screen, status: String::new() });
+            }
+        }
+
+        Ok(Recording { frames })
+    }
+}
+
+// Page through the frames, reading a command after each: Enter for the next
+// frame, p for the previous one, a number to jump to that frame, or q to
+// quit.
+pub fn replay(recording: &Recording, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let count = recording.frames.len();
+    if count == 0 {
+        return writeln!(output, "nothing was recorded");
+    }
+
+    let mut current = 0;
+    loop {
+        write!(output, "{}\r\n", recording.frames[current].to_terminal())?;
+        write!(output, "-- frame {}/{}: Enter next, p previous, <number> jump, q quit -- ", current + 1, count)?;
+        output.flush()?;
+
+        let mut command = String::new();
+        if input.read_line(&mut command)? == 0 {
+            break;
+        }
+        match command.trim() {
+            "q" => break,
+            "" if current + 1 == count => break,
+            "" => current += 1,
+            "p" => current = current.saturating_sub(1),
+            number => if let Ok(n) = number.parse::<usize>() {
+                current = n.clamp(1, count) - 1;
+            },
+        }
+    }
+
+    writeln!(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording() {
+        // nothing is kept, or even built, unless recording
+        snapshot("ignored", || panic!("built while not recording"));
+
+        start();
+        status(|| "starting".to_string());
+        snapshot("grid", || "ab\ncd\n".to_string());
+        status(|| "step 1".to_string());
+        let recording = finish();
+        assert!(!is_recording());
+
+        assert_eq!(recording.frames, [
+            Frame { screen: String::new(), status: "starting".to_string() },
+            Frame { screen: "ab\ncd\n".to_string(), status: "grid".to_string() },
+            Frame { screen: "ab\ncd\n".to_string(), status: "step 1".to_string() },
+        ]);
+        assert_eq!(recording.size(), (8, 3));
+
+        let cast = recording.to_cast("test", Duration::from_millis(500));
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines[0], r#"{"height":3,"title":"test","version":2,"width":8}"#);
+        assert_eq!(lines[3], r#"[1.0,"o","\u001b[H\u001b[2Jab\r\ncd\r\nstep 1"]"#);
+
+        let read = Recording::from_cast(&cast).unwrap();
+        assert_eq!(read.frames.len(), 3);
+        assert_eq!(read.frames[2].screen, "ab\ncd\nstep 1");
+        assert!(Recording::from_cast("{\"version\": 1}").is_err());
+
+        let mut output = Vec::new();
+        replay(&recording, "\n3\np\nq\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("-- frame ").count(), 4);
+        assert!(output.contains("-- frame 2/3") && output.contains("-- frame 3/3"));
+    }
+}
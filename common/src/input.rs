@@ -0,0 +1,104 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+// Where a puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    // already in memory, with a name to show in messages
+    Text { name: String, text: String },
+}
+
+impl Source {
+    // A command line argument: "-" means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::Path(PathBuf::from(arg))
+        }
+    }
+
+    // The first command line argument, or else default.
+    pub fn from_args_or(default: Source) -> Source {
+        match env::args().nth(1) {
+            Some(arg) => Source::from_arg(&arg),
+            None => default,
+        }
+    }
+
+    // A file in a day's directory.
+    pub fn in_dir(dir: impl AsRef<Path>, name: &str) -> Source {
+        Source::Path(dir.as_ref().join(name))
+    }
+
+    pub fn text(name: &str, text: &str) -> Source {
+        Source::Text { name: name.to_string(), text: text.to_string() }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        let read = match self {
+            Source::Path(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map(|_| text)
+            },
+            Source::Text { text, .. } => Ok(text.clone()),
+        };
+
+        read.map_err(|e| format!("couldn't read {}: {}", self, e))
+    }
+
+    // Open the input to read as it goes, for inputs too big to read at
+    // once.
+    pub fn open(&self) -> Result<Box<dyn BufRead + '_>, String> {
+        match self {
+            Source::Path(path) => fs::File::open(path).
+                map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>).
+                map_err(|e| format!("couldn't read {}: {}", self, e)),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::Text { text, .. } => Ok(Box::new(text.as_bytes())),
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Source::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Text { name, .. } => write!(f, "<{}>", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(Source::from_arg("day5/input.txt"), Source::Path(PathBuf::from("day5/input.txt")));
+
+        let source = Source::text("example", "1 2 3\n");
+        assert_eq!(source.read(), Ok("1 2 3\n".to_string()));
+        assert_eq!(source.to_string(), "<example>");
+        assert_eq!(source.open().unwrap().lines().count(), 1);
+
+        let missing = Source::in_dir(env!("CARGO_MANIFEST_DIR"), "no_such_input.txt");
+        assert!(missing.read().unwrap_err().starts_with("couldn't read "));
+        assert!(missing.open().is_err());
+    }
+}
@@ -0,0 +1,12 @@
+// Pieces shared by every day's solver.
+pub mod answer;
+pub mod config;
+pub mod differential;
+pub mod frames;
+pub mod input;
+pub mod logger;
+pub mod parse;
+pub mod runner;
+
+pub use answer::Solution;
+pub use parse::ParseError;
@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+use std::env;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+// Environment variable read by init_from_env(), using the same syntax as
+// Filter::from_str().
+pub const ENV_VAR: &str = "AOC_LOG";
+
+// Which log messages to show, with an optional level per day. A day is
+// matched by the crate the message came from, e.g. "day10".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub default: LevelFilter,
+    pub days: Vec<(String, LevelFilter)>,
+}
+
+impl Default for Filter {
+    // quiet: only the answers are printed
+    fn default() -> Self {
+        Filter { default: LevelFilter::Off, days: Vec::new() }
+    }
+}
+
+impl Filter {
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        let krate = target.split("::").next().unwrap_or(target);
+
+        self.days.iter().
+            find(|(day, _)| day == krate).
+            map_or(self.default, |(_, level)| *level)
+    }
+
+    pub fn max_level(&self) -> LevelFilter {
+        self.days.iter().map(|(_, level)| *level).fold(self.default, Ord::max)
+    }
+}
+
+fn parse_level(s: &str) -> Result<LevelFilter, String> {
+    match s {
+        "quiet" | "off" => Ok(LevelFilter::Off),
+        "error" => Ok(LevelFilter::Error),
+        "warn" => Ok(LevelFilter::Warn),
+        "info" => Ok(LevelFilter::Info),
+        "debug" => Ok(LevelFilter::Debug),
+        "trace" => Ok(LevelFilter::Trace),
+        _ => Err(format!("invalid log level '{}', expected quiet, info, debug or trace", s)),
+    }
+}
+
+// Parse a comma separated list of levels, each either for every day
+// ("debug") or for a single day ("10=trace" or "day10=trace"), e.g.
+// "info,day3=quiet,10=trace".
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+
+        for item in s.split(',').map(|item| item.trim()).filter(|item| !item.is_empty()) {
+            match item.split_once('=') {
+                None => filter.default = parse_level(item)?,
+                Some((day, level)) => {
+                    let number = day.trim_start_matches("day");
+                    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                        return Err(format!("invalid day '{}' in log filter", day));
+                    }
+                    filter.days.push((format!("day{}", number), parse_level(level)?));
+                },
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.default.as_str().to_lowercase())?;
+        for (day, level) in &self.days {
+            write!(f, ",{}={}", day, level.as_str().to_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    // The lines logged on this thread while capture() is running.
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Run f, collecting the lines it logs on this thread rather than writing
+// them to stderr, so that solvers running side by side don't interleave
+// their output.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    // put back any outer capture afterwards, even if f panics
+    struct Restore(Option<String>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CAPTURED.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(CAPTURED.replace(Some(String::new())));
+    let result = f();
+    let captured = CAPTURED.take().unwrap_or_default();
+
+    (result, captured)
+}
+
+// Writes each message to stderr as "[day10 DEBUG] ...", so that it never
+// mixes with the answers on stdout.
+struct StderrLogger {
+    filter: Filter,
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let krate = record.target().split("::").next().unwrap_or("");
+            let line = format!("[{} {}] {}\n", krate, record.level(), record.args());
+            CAPTURED.with_borrow_mut(|captured| match captured {
+                Some(captured) => captured.push_str(&line),
+                None => {
+                    let _ = io::stderr().lock().write_all(line.as_bytes());
+                },
+            });
+        }
+    }
+
+    fn flush(&self) {
+        let _ = io::stderr().flush();
+    }
+}
+
+// Install the logger. Only the first call has any effect.
+pub fn init(filter: Filter) {
+    let max_level = filter.max_level();
+    if log::set_boxed_logger(Box::new(StderrLogger { filter })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+// Install the logger using the filter in $AOC_LOG, or quiet if it isn't set.
+pub fn init_from_env() -> Result<(), String> {
+    let filter = match env::var(ENV_VAR) {
+        Ok(spec) => spec.parse::<Filter>().map_err(|e| format!("{}: {}", ENV_VAR, e))?,
+        Err(_) => Filter::default(),
+    };
+
+    init(filter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "info,day3=quiet,10=trace".parse().unwrap();
+        assert_eq!(filter.level_for("day3"), LevelFilter::Off);
+        assert_eq!(filter.level_for("day10::grid"), LevelFilter::Trace);
+        assert_eq!(filter.level_for("day7"), LevelFilter::Info);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+        assert_eq!(filter.to_string(), "info,day3=off,day10=trace");
+
+        assert_eq!("".parse::<Filter>(), Ok(Filter::default()));
+        assert!("loud".parse::<Filter>().is_err());
+        assert!("x=debug".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_capture() {
+        init("info".parse().unwrap());
+
+        let ((), outer) = capture(|| {
+            log::info!(target: "day3", "before");
+            let ((), inner) = capture(|| log::warn!(target: "day7::hands", "inner"));
+            assert_eq!(inner, "[day7 WARN] inner\n");
+            log::debug!(target: "day3", "hidden");
+            log::info!(target: "day3", "after");
+        });
+        assert_eq!(outer, "[day3 INFO] before\n[day3 INFO] after\n");
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use std::str::FromStr;
+
+use grid::Grid;
+
+// Input that a day's parser couldn't make sense of. Lines and columns are
+// counted from 1, and text is the piece of the line that was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // An error for input that stopped before something we needed.
+    pub fn at_end(day: u32, input: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line: input.lines().count() + 1,
+            column: 1,
+            text: String::new(),
+            message: message.into(),
+        }
+    }
+
+    // Render the error along with the offending line of input, pointing at
+    // the rejected text, e.g.
+    //
+    //   error: day 2: expected a number
+    //    --> line 3, column 6
+    //     |
+    //   3 | Game x: 3 blue
+    //     |      ^
+    pub fn render(&self, input: &str) -> String {
+        let source = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = self.line.to_string().len();
+        let width = self.text.chars().count().max(1);
+
+        format!("error: day {}: {}\n{:>gutter$}--> line {}, column {}\n{:>gutter$} |\n{} | {}\n{:>gutter$} | {}{}",
+                self.day, self.message,
+                "", self.line, self.column,
+                "",
+                self.line, source,
+                "", " ".repeat(self.column - 1), "^".repeat(width),
+                gutter = gutter)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: {}", self.day, self.line, self.column, self.message)?;
+        if !self.text.is_empty() {
+            write!(f, " (found '{}')", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// A single line of a day's input, remembering where it came from so that
+// errors can point back at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub day: u32,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+// Number the lines of input, starting at 1.
+pub fn lines(day: u32, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(i, text)| Line { day, number: i + 1, text })
+}
+
+impl<'a> Line<'a> {
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    // Column of token within this line, if token was sliced from it.
+    fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = token.as_ptr() as usize;
+
+        if pos >= start && pos <= start + self.text.len() {
+            self.text[..pos - start].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    // An error pointing at token, which should be a slice of this line.
+    pub fn error(&self, token: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            day: self.day,
+            line: self.number,
+            column: self.column(token),
+            text: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn number<T: FromStr>(&self, token: &'a str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, "expected a number"))
+    }
+
+    // Parse every whitespace separated number in s.
+    pub fn numbers<T: FromStr>(&self, s: &'a str) -> Result<Vec<T>, ParseError> {
+        s.split_whitespace().map(|token| self.number(token)).collect()
+    }
+
+    // Split s (a slice of this line) around the first delimiter.
+    pub fn split_once(&self, s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter).
+            ok_or_else(|| self.error(&s[s.len()..], format!("expected '{}'", delimiter)))
+    }
+
+    // Strip a required prefix from s (a slice of this line).
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix).
+            ok_or_else(|| self.error(s, format!("expected '{}'", prefix)))
+    }
+}
+
+// Load a grid from text, converting each character with f, which returns
+// None for characters that aren't allowed. Every line must be as long as the
+// first one.
+pub fn grid<T>(day: u32, input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+    let mut num_cols = None;
+
+    // a trailing newline doesn't start another row
+    for line in lines(day, input.trim_end_matches(['\n', '\r'])) {
+        let len = line.text.chars().count();
+        match num_cols {
+            None => num_cols = Some(len),
+            Some(n) if n != len => {
+                return Err(line.error(line.text, format!("expected {} columns, found {}", n, len)));
+            },
+            _ => {},
+        }
+
+        for (i, c) in line.text.char_indices() {
+            if f(c).is_none() {
+                return Err(line.error(&line.text[i..i + c.len_utf8()], "unexpected character"));
+            }
+        }
+    }
+
+    // every line has been checked, so this can't fail
+    Ok(Grid::parse_with(input, |c| f(c).unwrap()).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_errors() {
+        let line = lines(2, "Game 1: 3 blue\nGame x: 4 red").nth(1).unwrap();
+        let (game, _) = line.split_once(line.text, ":").unwrap();
+        let id = line.strip_prefix(game, "Game ").unwrap();
+
+        let e = line.number::<u32>(id).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 6, "x"));
+        assert_eq!(e.to_string(), "day 2, line 2, column 6: expected a number (found 'x')");
+        assert_eq!(e.render("Game 1: 3 blue\nGame x: 4 red"),
+                   "error: day 2: expected a number\n --> line 2, column 6\n  |\n2 | Game x: 4 red\n  |      ^");
+
+        let e = line.split_once(line.text, "|").unwrap_err();
+        assert_eq!((e.column, e.message.as_str()), (14, "expected '|'"));
+    }
+
+    #[test]
+    fn test_grid() {
+        let g = grid(10, "S-7\n|.|\n\n", |c| "S-7|.".contains(c).then_some(c)).unwrap();
+        assert_eq!((g.num_rows(), g.num_columns()), (2, 3));
+
+        let e = grid(10, "S-7\n|x|\n", |c| "S-7|.".contains(c).then_some(c)).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "x"));
+
+        let e = grid(10, "S-7\n|.\n", Some).unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 1, "expected 3 columns, found 2"));
+    }
+}
@@ -0,0 +1,75 @@
+// What every day's own binary does: start the logger, load the day's
+// settings from aoc.toml, read and parse its input (the first argument, or
+// else the configured input), and print both parts' answers. Anything that
+// goes wrong is printed and the binary exits with status 1.
+use std::process;
+
+use crate::config::{self, DayConfig, Params};
+use crate::input::Source;
+use crate::{logger, ParseError, Solution};
+
+// Runs a day that takes no parameters.
+pub fn run<I, A: Solution, B: Solution>(manifest_dir: &str,
+                                        day: u32,
+                                        parse: impl Fn(&str) -> Result<I, ParseError>,
+                                        part1: impl Fn(&I) -> A,
+                                        part2: impl Fn(&I) -> B) {
+    run_with(manifest_dir, day, parse, |params| params.only(&[]), |input, _| part1(input), |input, _| part2(input));
+}
+
+// Runs a day whose parts take the parameters from_config makes out of its
+// [dayN.params].
+pub fn run_with<I, P, A: Solution, B: Solution>(manifest_dir: &str,
+                                                day: u32,
+                                                parse: impl Fn(&str) -> Result<I, ParseError>,
+                                                from_config: impl Fn(&Params) -> Result<P, String>,
+                                                part1: impl Fn(&I, &P) -> A,
+                                                part2: impl Fn(&I, &P) -> B) {
+    let config = setup(manifest_dir, day);
+    let params = params(day, &config, from_config);
+    let input = read(&config.source(manifest_dir), parse);
+    print_answers([part1(&input, &params).answer(), part2(&input, &params).answer()]);
+}
+
+// Starts the logger and loads the day's config.
+pub fn setup(manifest_dir: &str, day: u32) -> DayConfig {
+    or_exit(logger::init_from_env());
+    or_exit(config::for_day(manifest_dir, day))
+}
+
+pub fn params<P>(day: u32, config: &DayConfig, from_config: impl Fn(&Params) -> Result<P, String>) -> P {
+    or_exit(from_config(&config.params).map_err(|e| format!("day{}: {}", day, e)))
+}
+
+// Reads and parses the input, pointing at where in it parsing went wrong.
+pub fn read<I>(source: &Source, parse: impl Fn(&str) -> Result<I, ParseError>) -> I {
+    let contents = or_exit(source.read());
+    or_exit(parse(&contents).map_err(|e| format!("{}\n  = in {}", e.render(&contents), source)))
+}
+
+// Prints each part's answer, then exits if either part couldn't find one.
+pub fn print_answers(answers: [Result<String, String>; 2]) {
+    let mut failed = false;
+    for (part, answer) in (1..).zip(answers) {
+        match answer {
+            Ok(answer) => println!("Part {} answer: {}", part, answer),
+            Err(e) => {
+                eprintln!("part {}: {}", part, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+pub fn or_exit<T>(result: Result<T, String>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// What was read from each line, to check the calibration values by hand:
+// the first and last tokens, where they are and what text they came from,
+// which lines had nothing to read at all, and which were only read by
+// taking a misspelling for a number word.
+use crate::matcher::{Kind, Matcher, Mode, Token};
+use crate::Input;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineAudit<'a> {
+    // counting from 1
+    pub number: usize,
+    pub line: &'a str,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+    pub value: u32,
+}
+
+impl LineAudit<'_> {
+    // A line without a digit or number word, which counts as 0.
+    pub fn has_warning(&self) -> bool {
+        self.first.is_none()
+    }
+
+    // The text a token was read from, such as "eight" or "8".
+    pub fn text(&self, token: &Token) -> &str {
+        &self.line[token.span.clone()]
+    }
+
+    // The tokens read from misspelt words.
+    pub fn repairs(&self) -> Vec<&Token> {
+        let mut repairs: Vec<&Token> = [&self.first, &self.last].into_iter().
+            flatten().
+            filter(|token| matches!(token.kind, Kind::Repaired { .. })).
+            collect();
+        // a line with just the one token has it first and last
+        repairs.dedup();
+        repairs
+    }
+}
+
+pub fn audit<'a>(input: &'a Input, matcher: &Matcher, mode: Mode) -> Vec<LineAudit<'a>> {
+    let mut audits = Vec::new();
+
+    for (i, line) in input.iter().enumerate() {
+        let first = matcher.first(line.as_bytes());
+        let last = matcher.last(line.as_bytes(), mode);
+        let value = match (&first, &last) {
+            (Some(first), Some(last)) => first.value * 10 + last.value,
+            _ => 0,
+        };
+        audits.push(LineAudit { number: i + 1, line, first, last, value });
+    }
+
+    audits
+}
+
+pub const CSV_HEADER: &str =
+    "part,line,text,first,first_kind,first_start,first_end,last,last_kind,last_start,last_end,value,warning";
+
+// Quote a field if it has anything CSV would otherwise split on.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// The audits of one part as CSV rows, without the header. Token positions
+// are byte offsets into the line, the end one past the token's last byte,
+// and are left empty when there's no token.
+pub fn to_csv(part: u32, audits: &[LineAudit]) -> String {
+    let mut csv = String::new();
+
+    for audit in audits {
+        let mut fields = vec![part.to_string(), audit.number.to_string(), csv_field(audit.line)];
+        for token in [&audit.first, &audit.last] {
+            match token {
+                Some(token) => fields.extend([
+                    csv_field(audit.text(token)),
+                    match token.kind {
+                        Kind::Digit => "digit",
+                        Kind::Word => "word",
+                        Kind::Repaired { .. } => "repaired",
+                    }.to_string(),
+                    token.span.start.to_string(),
+                    token.span.end.to_string(),
+                ]),
+                None => fields.extend(["", "", "", ""].map(String::from)),
+            }
+        }
+        fields.push(audit.value.to_string());
+        fields.push(audit.has_warning().to_string());
+
+        csv += &fields.join(",");
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// A line for the audits saying how many lines there were and which ones
+// had no tokens.
+pub fn summary(part: u32, audits: &[LineAudit]) -> String {
+    let warnings: Vec<String> = audits.iter().
+        filter(|audit| audit.has_warning()).
+        map(|audit| audit.number.to_string()).
+        collect();
+
+    match warnings.len() {
+        0 => format!("part {}: {} line{}, every one with a digit or number word",
+                     part, audits.len(), if audits.len() == 1 { "" } else { "s" }),
+        n => format!("part {}: {} lines, {} with no digit or number word, counted as 0 (line{} {})",
+                     part, audits.len(), n, if n == 1 { "" } else { "s" }, warnings.join(", ")),
+    }
+}
+
+// A line for each line of audits with a repair, saying what was taken for
+// which word and what the line would have been worth otherwise, according
+// to exact, the same lines read without repairs. words is the vocabulary
+// the matcher was given.
+pub fn describe_repairs(audits: &[LineAudit], exact: &[LineAudit], words: &[(String, u32)]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for (audit, exact) in audits.iter().zip(exact) {
+        let repairs = audit.repairs();
+        if repairs.is_empty() {
+            continue;
+        }
+
+        let mut how = Vec::new();
+        for token in repairs {
+            if let Kind::Repaired { word, edits } = token.kind {
+                how.push(format!("'{}' at {}..{} as {} ({} edit{})", audit.text(token), token.span.start, token.span.end,
+                                 words[word].0, edits, if edits == 1 { "" } else { "s" }));
+            }
+        }
+        lines.push(format!("line {}: read {}, worth {} rather than {}", audit.number, how.join(" and "), audit.value, exact.value));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary::ENGLISH;
+
+    #[test]
+    fn test_audit() {
+        let input = crate::parse("two1nine\n\nno numbers, \"here\"\n7pqrstsixteen\n").unwrap();
+        let audits = audit(&input, &Matcher::new(&ENGLISH), Mode::Overlapping);
+        assert_eq!(audits.len(), 4);
+        assert_eq!(audits[0].value, 29);
+        assert_eq!(audits[0].text(audits[0].first.as_ref().unwrap()), "two");
+        assert_eq!(audits[3].last, Some(Token { value: 6, span: 6..9, kind: Kind::Word }));
+        let warnings: Vec<usize> = audits.iter().filter(|audit| audit.has_warning()).map(|audit| audit.number).collect();
+        assert_eq!(warnings, [2, 3]);
+
+        let csv = to_csv(2, &audits);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "2,1,two1nine,two,word,0,3,nine,word,4,8,29,false");
+        assert_eq!(rows[1], "2,2,,,,,,,,,,0,true");
+        assert_eq!(rows[2], "2,3,\"no numbers, \"\"here\"\"\",,,,,,,,,0,true");
+        assert_eq!(rows[3], "2,4,7pqrstsixteen,7,digit,0,1,six,word,6,9,76,false");
+        assert_eq!(rows[0].split(',').count(), CSV_HEADER.split(',').count());
+
+        assert_eq!(summary(2, &audits), "part 2: 4 lines, 2 with no digit or number word, counted as 0 (lines 2, 3)");
+        assert_eq!(summary(2, &audits[..1]), "part 2: 1 line, every one with a digit or number word");
+    }
+
+    #[test]
+    fn test_repairs() {
+        let input = crate::parse("seveen2
+nien
+8five
+thre2fuor
+").unwrap();
+        let words = crate::vocabulary::Vocabulary::default().words;
+        let exact = audit(&input, &Matcher::new(&words), Mode::Overlapping);
+        let repaired = audit(&input, &Matcher::new(&words).fuzzy(1), Mode::Overlapping);
+        assert_eq!(repaired[1].repairs().len(), 1);
+        assert_eq!(repaired.iter().map(|audit| audit.value).collect::<Vec<_>>(), [72, 99, 85, 34]);
+
+        assert_eq!(describe_repairs(&repaired, &exact, &words), [
+            "line 1: read 'seveen' at 0..6 as seven (1 edit), worth 72 rather than 22",
+            "line 2: read 'nien' at 0..4 as nine (1 edit), worth 99 rather than 0",
+            "line 4: read 'thre' at 0..4 as three (1 edit) and 'fuor' at 5..9 as four (1 edit), worth 34 rather than 22",
+        ]);
+        assert!(to_csv(2, &repaired).contains(",seveen,repaired,0,6,"));
+
+        // a repair covers whole letters, so its text can be shown
+        let german = crate::vocabulary::Vocabulary::pack("german").unwrap().words;
+        let input = crate::parse("fünü\n").unwrap();
+        let repaired = audit(&input, &Matcher::new(&german).fuzzy(1), Mode::Overlapping);
+        let exact = audit(&input, &Matcher::new(&german), Mode::Overlapping);
+        assert_eq!(describe_repairs(&repaired, &exact, &german), ["line 1: read 'fünü' at 0..6 as fünf (1 edit), worth 55 rather than 0"]);
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::Path;
+
+use common::{config, ParseError};
+use log::debug;
+
+pub mod audit;
+pub mod matcher;
+pub mod stream;
+pub mod vocabulary;
+
+use audit::LineAudit;
+use matcher::{Matcher, Mode};
+use vocabulary::{Vocabulary, PACKS};
+
+pub const DAY: u32 = 1;
+
+// Each line of the calibration document is kept as-is, we'll search for the
+// digits (and number words for part 2) when solving.
+pub type Input = Vec<String>;
+// u64, like stream::Totals, so both add up a document of billions of lines
+// the same
+pub type Answer = u64;
+
+// Any text is a valid calibration document, lines without digits are worth 0.
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    Ok(input.lines().map(|line| line.to_string()).collect())
+}
+
+// Find the first and last token of a line and combine them into a two-digit
+// calibration value, or 0 if there are none.
+fn calibration_value(line: &str, matcher: &Matcher, mode: Mode) -> u32 {
+    let first = matcher.first(line.as_bytes());
+    let last = matcher.last(line.as_bytes(), mode);
+
+    match (first, last) {
+        (Some(first), Some(last)) => first.value * 10 + last.value,
+        _ => 0,
+    }
+}
+
+fn calibrate(input: &Input, matcher: &Matcher, mode: Mode) -> Answer {
+    // mutable var for running sum
+    let mut sum = 0;
+
+    for line in input {
+        let value = calibration_value(line, matcher, mode);
+        sum += value as Answer;
+        debug!("{} {} {} {}", line, value / 10, value % 10, sum);
+    }
+
+    sum
+}
+
+// The number words part 2 looks for, how it counts words that share
+// letters, and how many edits away a misspelt word can be and still be read
+// (0 to only read words spelt right).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub mode: Mode,
+    pub vocabulary: Vocabulary,
+    pub fuzzy: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { mode: Mode::Overlapping, vocabulary: Vocabulary::default(), fuzzy: 0 }
+    }
+}
+
+impl Params {
+    // words names one of the vocabulary packs, or words_file is a word list
+    // to read instead. Config::load has already made words_file relative to
+    // aoc.toml rather than to where we're run from.
+    pub fn from_config(params: &config::Params) -> Result<Params, String> {
+        params.only(&["mode", "words", "words_file", "fuzzy"])?;
+
+        let mode = match params.string("mode", "overlapping")?.as_str() {
+            "overlapping" => Mode::Overlapping,
+            "non-overlapping" => Mode::NonOverlapping,
+            other => return Err(format!("mode should be overlapping or non-overlapping, not {}", other)),
+        };
+
+        let pack = params.string("words", "")?;
+        let file = params.string("words_file", "")?;
+        let vocabulary = match (pack.as_str(), file.as_str()) {
+            ("", "") => Vocabulary::default(),
+            (pack, "") => Vocabulary::pack(pack).
+                ok_or_else(|| format!("words should be one of {}, not {}", PACKS.join(", "), pack))?,
+            ("", file) => Vocabulary::load(Path::new(file))?,
+            _ => return Err("set either words or words_file, not both".to_string()),
+        };
+
+        Ok(Params { mode, vocabulary, fuzzy: params.int("fuzzy", 0)? })
+    }
+
+    // The matcher for part 2.
+    pub fn matcher(&self) -> Matcher {
+        Matcher::new(&self.vocabulary.words).fuzzy(self.fuzzy)
+    }
+}
+
+pub fn part1(input: &Input) -> Answer {
+    calibrate(input, &Matcher::digits(), Mode::Overlapping)
+}
+
+// digits never overlap, so there's nothing to set
+pub fn part1_with(input: &Input, _params: &Params) -> Answer {
+    part1(input)
+}
+
+pub fn part2(input: &Input) -> Answer {
+    part2_with(input, &Params::default())
+}
+
+pub fn part2_with(input: &Input, params: &Params) -> Answer {
+    // number words can run into each other, as in "eightwo". Overlapping,
+    // the puzzle's way, that's 8 at the start of a line and 2 at the end.
+    calibrate(input, &params.matcher(), params.mode)
+}
+
+// What part 1 reads from each line.
+pub fn audit_part1(input: &Input) -> Vec<LineAudit<'_>> {
+    audit::audit(input, &Matcher::digits(), Mode::Overlapping)
+}
+
+// What part 2 reads from each line.
+pub fn audit_part2<'a>(input: &'a Input, params: &Params) -> Vec<LineAudit<'a>> {
+    audit::audit(input, &params.matcher(), params.mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 142);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 142);
+        assert_eq!(super::part2(&example("input_test2.txt")), 281);
+    }
+
+    #[test]
+    fn test_params() {
+        let params = super::Params { mode: super::Mode::NonOverlapping, ..Default::default() };
+        let input = super::parse("eightwo\nzoneight234\n").unwrap();
+        assert_eq!(super::part2(&input), 82 + 14);
+        assert_eq!(super::part2_with(&input, &params), 88 + 14);
+
+        let german = super::parse("einszwei\nnullachtneun\n").unwrap();
+        let mut settings = common::config::Params::default();
+        settings.set("words", "german".into());
+        let params = super::Params::from_config(&settings).unwrap();
+        assert_eq!(super::part2_with(&german, &params), 12 + 9);
+        settings.set("fuzzy", 1.into());
+        let params = super::Params::from_config(&settings).unwrap();
+        assert_eq!(params.fuzzy, 1);
+        assert_eq!(super::part2_with(&super::parse("zwie3\n").unwrap(), &params), 23);
+        settings.set("words_file", "words.txt".into());
+        assert!(super::Params::from_config(&settings).is_err());
+    }
+}
@@ -1,96 +1,106 @@
+use std::env;
 use std::fs;
+use std::process;
+use std::time::Instant;
 
-fn main() {
-    println!("############################");
-    println!("          Part 1");
-    println!("############################");
-    part1();
-
-    println!("############################");
-    println!("          Part 2");
-    println!("############################");
-    part2();
+use common::input::Source;
+use common::runner::{self, or_exit};
+use common::Solution;
+
+const USAGE: &str = "usage: day1 [INPUT] [--audit CSV | --stream]
+
+--audit says which lines had no digit or number word and writes what each
+part read from every line to CSV, or to stdout if CSV is -.
+
+--stream solves both parts as the input is read, without holding it all in
+memory, and says how fast that went.";
+
+#[derive(Default)]
+struct Options {
+    input: Option<Source>,
+    // where to write the audit
+    audit: Option<String>,
+    stream: bool,
 }
 
-fn part1() {
-    let binding = fs::read_to_string("input.txt").
-        expect("Something went wrong reading the file");
-    let contents = binding.split("\n");
-    
-    // mutable var for running sum
-    let mut sum = 0;
-
-    for line in contents {
-        let mut first_number = 0;
-        let mut second_number = 0;
-
-        // search from beginning to find first number
-        for i in line.chars() {
-            // is a number?
-            if i.is_digit(10) {
-                // convert char to integer
-                first_number = i.to_digit(10).unwrap();
-                break;
-            }
-        }
-        // search from end to find last number
-        for i in line.chars().rev() {
-            if i.is_digit(10) {
-                second_number = i.to_digit(10).unwrap();
-                break;
-            }
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--audit" => options.audit = Some(args.next().ok_or("--audit needs a file to write")?),
+            "--stream" => options.stream = true,
+            _ if options.input.is_none() => options.input = Some(Source::from_arg(&arg)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
         }
+    }
 
-        sum += (first_number * 10) + second_number;
-        println!("{} {} {} {}", line, first_number, second_number, sum);
+    if options.stream && options.audit.is_some() {
+        return Err("--audit needs the whole input, so can't be used with --stream".to_string());
     }
+    Ok(options)
 }
 
-fn part2() {
-    // in each line, we'll do string substitution to replace number "words"
-    // with their respective digit, then re-run the same logic as part 1.
-    // this can cause problems if the number words are part of other words,
-    // i.e. eightwo. In this case we'll want "eight", since it comes first,
-    // unless its at the end, then we'll want "two". To get around this we'll
-    // preserve the first and last letters of each number word after substitution.
-    // So oneight becomes "o1eight" after substituting "one" and then "o1e8t"
-    // after substituting "eight". So this gives the correct result of 18.
-    let binding = fs::read_to_string("input.txt").
-        expect("Something went wrong reading the file").
-        replace("one", "o1e").
-        replace("two", "t2o").
-        replace("three", "t3e").
-        replace("four", "f4r").
-        replace("five", "f5e").
-        replace("six", "s6x").
-        replace("seven", "s7n").
-        replace("eight", "e8t").
-        replace("nine", "n9e");
-
-    let contents = binding.split("\n");
-
-    let mut sum = 0;
-
-    for line in contents {
-        let mut first_number = 0;
-        let mut second_number = 0;
-
-        for i in line.chars() {
-            if i.is_digit(10) {
-                first_number = i.to_digit(10).unwrap();
-                break;
-            }
+fn stream(source: &Source, params: &day1::Params) {
+    let start = Instant::now();
+    let totals = or_exit(source.open().and_then(|reader| day1::stream::solve(reader, params).map_err(|e| e.to_string())));
+    let elapsed = start.elapsed();
+
+    println!("Part 1 answer: {}", totals.part1);
+    println!("Part 2 answer: {}", totals.part2);
+    println!("Read {} lines, {} bytes in {:.3}s ({:.1} MB/s)",
+             totals.lines, totals.bytes, elapsed.as_secs_f64(),
+             totals.bytes as f64 / 1e6 / elapsed.as_secs_f64().max(1e-9));
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
         }
+    };
+
+    let config = runner::setup(env!("CARGO_MANIFEST_DIR"), day1::DAY);
+    let params = runner::params(day1::DAY, &config, day1::Params::from_config);
+    let source = options.input.unwrap_or_else(|| config.default_source(env!("CARGO_MANIFEST_DIR")));
+    if options.stream {
+        stream(&source, &params);
+        return;
+    }
+
+    let input = runner::read(&source, day1::parse);
+    runner::print_answers([day1::part1_with(&input, &params).answer(), day1::part2_with(&input, &params).answer()]);
 
-        for i in line.chars().rev() {
-            if i.is_digit(10) {
-                second_number = i.to_digit(10).unwrap();
-                break;
-            }
+    if params.fuzzy > 0 {
+        let exact = day1::Params { fuzzy: 0, ..params.clone() };
+        let repairs = day1::audit::describe_repairs(&day1::audit_part2(&input, &params),
+                                                    &day1::audit_part2(&input, &exact),
+                                                    &params.vocabulary.words);
+        println!("Repaired {} line{} with misspelt number words", repairs.len(), if repairs.len() == 1 { "" } else { "s" });
+        for repair in repairs {
+            println!("  {}", repair);
         }
+    }
+
+    if let Some(path) = options.audit {
+        let audits = [day1::audit_part1(&input), day1::audit_part2(&input, &params)];
 
-        sum += (first_number * 10) + second_number;
+        println!("############################");
+        println!("          Audit");
+        println!("############################");
+        let mut csv = format!("{}\n", day1::audit::CSV_HEADER);
+        for (part, audits) in (1..).zip(&audits) {
+            println!("{}", day1::audit::summary(part, audits));
+            csv += &day1::audit::to_csv(part, audits);
+        }
 
-        println!("{} {} {} {}", line, first_number, second_number, sum);
+        if path == "-" {
+            print!("{}", csv);
+        } else {
+            or_exit(fs::write(&path, csv).map_err(|e| format!("couldn't write {}: {}", path, e)));
+        }
     }
 }
@@ -0,0 +1,465 @@
+// Finding the digits and number words in a line with an Aho-Corasick
+// automaton, so every word is looked for in a single pass over the line
+// however many there are, and words that run into each other ("eightwo")
+// are all seen.
+//
+// Misspelt words ("seveen", "nien") can be read too, up to a number of
+// edits away from a word, where an edit is putting in, taking out or
+// changing a letter, or swapping two next to each other. Short words would
+// be found all over the place that way, so a word gets one edit for every
+// 4 letters it has, up to the number allowed: none for "one", one for
+// "seven". When there's a choice, the token read is decided by, in order:
+//
+//   1. digits and correctly spelt words, which a misspelling can't overlap,
+//      so one only counts before the first of them or after the last
+//   2. being first (starting earliest) or last (ending latest)
+//   3. needing the fewest edits
+//   4. covering the most text
+//   5. being listed earliest in the vocabulary
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::str;
+
+// How tokens that share letters are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // every occurrence counts, so "eightwo" is 8 then 2
+    Overlapping,
+    // reading left to right, a token's letters can't be part of the next
+    // one, so "eightwo" is just 8
+    NonOverlapping,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Digit,
+    Word,
+    // a misspelling of the matcher's word with this index, edits away from
+    // it
+    Repaired { word: usize, edits: usize },
+}
+
+// A digit or number word found in a line, with where it is in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub value: u32,
+    pub span: Range<usize>,
+    pub kind: Kind,
+}
+
+// A dense automaton: the next state for every state and byte, with the
+// failure links already followed.
+struct Automaton {
+    next: Vec<[u32; 256]>,
+    // how many bytes of a pattern each state has matched
+    depth: Vec<usize>,
+    // the longest pattern that ends at each state
+    longest: Vec<Option<usize>>,
+}
+
+const NONE: u32 = u32::MAX;
+
+impl Automaton {
+    // Patterns appearing more than once keep their first index.
+    fn new<'a>(patterns: impl Iterator<Item = &'a [u8]>) -> Automaton {
+        let mut automaton = Automaton { next: vec![[NONE; 256]], depth: vec![0], longest: vec![None] };
+
+        // the trie of all the patterns
+        for (i, pattern) in patterns.enumerate() {
+            let mut state = 0;
+            for &b in pattern {
+                if automaton.next[state][b as usize] == NONE {
+                    automaton.next[state][b as usize] = automaton.next.len() as u32;
+                    automaton.next.push([NONE; 256]);
+                    automaton.depth.push(automaton.depth[state] + 1);
+                    automaton.longest.push(None);
+                }
+                state = automaton.next[state][b as usize] as usize;
+            }
+            automaton.longest[state].get_or_insert(i);
+        }
+
+        // then fill in the missing transitions breadth first, where each
+        // state's failure state has already been done
+        let mut fail = vec![0; automaton.next.len()];
+        let mut to_visit = VecDeque::from([0]);
+        while let Some(state) = to_visit.pop_front() {
+            for b in 0..256 {
+                let child = automaton.next[state][b];
+                if child == NONE {
+                    automaton.next[state][b] = if state == 0 { 0 } else { automaton.next[fail[state]][b] };
+                    continue;
+                }
+
+                let child = child as usize;
+                fail[child] = if state == 0 { 0 } else { automaton.next[fail[state]][b] as usize };
+                if automaton.longest[child].is_none() {
+                    automaton.longest[child] = automaton.longest[fail[child]];
+                }
+                to_visit.push_back(child);
+            }
+        }
+
+        automaton
+    }
+
+    // The pattern that starts first in bytes, the longest if several start
+    // at the same place, as (pattern, start) counting from the first byte.
+    fn leftmost(&self, bytes: impl Iterator<Item = u8>, lengths: &[usize]) -> Option<(usize, usize)> {
+        let mut state = 0;
+        let mut best: Option<(usize, usize)> = None;
+
+        for (i, b) in bytes.enumerate() {
+            state = self.next[state][b as usize] as usize;
+
+            // the longest pattern ending here is the one starting earliest
+            if let Some(pattern) = self.longest[state] {
+                let start = i + 1 - lengths[pattern];
+                let better = match best {
+                    None => true,
+                    Some((best_pattern, best_start)) =>
+                        start < best_start || (start == best_start && lengths[pattern] > lengths[best_pattern]),
+                };
+                if better {
+                    best = Some((pattern, start));
+                }
+            }
+
+            // stop once no pattern still being matched could start as early
+            if best.is_some_and(|(_, best_start)| i + 1 - self.depth[state] > best_start) {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+struct Pattern {
+    value: u32,
+    len: usize,
+    kind: Kind,
+}
+
+// Words longer than this (in letters) are only read when spelt right,
+// which keeps working out edits to a fixed amount of memory.
+const MAX_FUZZY_LEN: usize = 32;
+
+// Room for the letters of a word or a misspelling of it, and one more.
+const EDIT_WIDTH: usize = MAX_FUZZY_LEN * 2 + 1;
+
+// Looks for the digits 0-9 and a set of number words.
+pub struct Matcher {
+    patterns: Vec<Pattern>,
+    lengths: Vec<usize>,
+    forward: Automaton,
+    // the same patterns spelt backwards, to search from the end of a line
+    backward: Automaton,
+    words: Vec<(String, u32)>,
+    max_edits: usize,
+}
+
+impl Matcher {
+    // Just the digits.
+    pub fn digits() -> Matcher {
+        Matcher::new::<&str>(&[])
+    }
+
+    // The digits and the given words with their values. Where a word is
+    // given twice, the first value is used.
+    pub fn new<S: AsRef<str>>(words: &[(S, u32)]) -> Matcher {
+        let digits: Vec<String> = (0..10).map(|d| d.to_string()).collect();
+        let mut texts: Vec<&[u8]> = Vec::new();
+        let mut patterns = Vec::new();
+        for (d, digit) in digits.iter().enumerate() {
+            texts.push(digit.as_bytes());
+            patterns.push(Pattern { value: d as u32, len: 1, kind: Kind::Digit });
+        }
+        for (word, value) in words {
+            texts.push(word.as_ref().as_bytes());
+            patterns.push(Pattern { value: *value, len: word.as_ref().len(), kind: Kind::Word });
+        }
+
+        let reversed: Vec<Vec<u8>> = texts.iter().map(|text| text.iter().rev().copied().collect()).collect();
+        Matcher {
+            lengths: patterns.iter().map(|pattern| pattern.len).collect(),
+            patterns,
+            forward: Automaton::new(texts.into_iter()),
+            backward: Automaton::new(reversed.iter().map(|text| text.as_slice())),
+            words: words.iter().map(|(word, value)| (word.as_ref().to_string(), *value)).collect(),
+            max_edits: 0,
+        }
+    }
+
+    // Read misspelt words as well, up to max_edits away from a word.
+    pub fn fuzzy(mut self, max_edits: usize) -> Matcher {
+        self.max_edits = max_edits;
+        self
+    }
+
+    fn allowed_edits(&self, word: &str) -> usize {
+        let len = word.chars().count();
+        if len > MAX_FUZZY_LEN { 0 } else { self.max_edits.min(len / 4) }
+    }
+
+    // The word text is a misspelling of, as (word, edits), taking the one
+    // fewest edits away and then the first listed. Edits are counted in
+    // letters, so changing "ü" to "u" is one, and text that isn't UTF-8
+    // isn't a misspelling of anything.
+    fn misspelling(&self, text: &[u8]) -> Option<(usize, usize)> {
+        let text = str::from_utf8(text).ok()?;
+        let text_len = text.chars().count();
+        let mut best: Option<(usize, usize)> = None;
+
+        for (i, (word, _)) in self.words.iter().enumerate() {
+            let allowed = self.allowed_edits(word);
+            if allowed == 0 || text_len.abs_diff(word.chars().count()) > allowed {
+                continue;
+            }
+            let edits = edit_distance(text, word);
+            if (1..=allowed).contains(&edits) && best.is_none_or(|(_, best_edits)| edits < best_edits) {
+                best = Some((i, edits));
+            }
+        }
+
+        best
+    }
+
+    fn repaired(&self, start: usize, end: usize, (word, edits): (usize, usize)) -> Token {
+        Token { value: self.words[word].1, span: start..end, kind: Kind::Repaired { word, edits } }
+    }
+
+    // The most bytes a misspelling could cover, each edit adding at most a
+    // 4 byte letter.
+    fn max_misspelling_len(&self) -> usize {
+        self.words.iter().map(|(word, _)| word.len() + self.allowed_edits(word) * 4).max().unwrap_or(0)
+    }
+
+    // The first misspelling that ends by limit.
+    fn first_repair(&self, line: &[u8], limit: usize) -> Option<Token> {
+        let max_len = self.max_misspelling_len();
+
+        for start in (0..limit).filter(|&start| is_char_boundary(line, start)) {
+            let mut best: Option<Token> = None;
+            // longest first, so only fewer edits replace it
+            for end in (start + 1..=limit.min(start + max_len)).rev().filter(|&end| is_char_boundary(line, end)) {
+                if let Some(found) = self.misspelling(&line[start..end]) {
+                    if best.as_ref().is_none_or(|best| matches!(best.kind, Kind::Repaired { edits, .. } if found.1 < edits)) {
+                        best = Some(self.repaired(start, end, found));
+                    }
+                }
+            }
+            if best.is_some() {
+                return best;
+            }
+        }
+
+        None
+    }
+
+    // The last misspelling that starts from limit on.
+    fn last_repair(&self, line: &[u8], limit: usize) -> Option<Token> {
+        let max_len = self.max_misspelling_len();
+
+        for end in (limit + 1..=line.len()).rev().filter(|&end| is_char_boundary(line, end)) {
+            let mut best: Option<Token> = None;
+            for start in (end.saturating_sub(max_len).max(limit)..end).filter(|&start| is_char_boundary(line, start)) {
+                if let Some(found) = self.misspelling(&line[start..end]) {
+                    if best.as_ref().is_none_or(|best| matches!(best.kind, Kind::Repaired { edits, .. } if found.1 < edits)) {
+                        best = Some(self.repaired(start, end, found));
+                    }
+                }
+            }
+            if best.is_some() {
+                return best;
+            }
+        }
+
+        None
+    }
+
+    fn token(&self, pattern: usize, start: usize) -> Token {
+        let pattern = &self.patterns[pattern];
+        Token { value: pattern.value, span: start..start + pattern.len, kind: pattern.kind }
+    }
+
+    // The token starting first at or after from, the longest if several
+    // start together.
+    pub fn find_at(&self, line: &[u8], from: usize) -> Option<Token> {
+        self.forward.leftmost(line[from..].iter().copied(), &self.lengths).
+            map(|(pattern, start)| self.token(pattern, from + start))
+    }
+
+    // The first token in the line, which is the same in either mode.
+    pub fn first(&self, line: &[u8]) -> Option<Token> {
+        let exact = self.find_at(line, 0);
+        if self.max_edits == 0 {
+            return exact;
+        }
+
+        let limit = exact.as_ref().map_or(line.len(), |token| token.span.start);
+        self.first_repair(line, limit).or(exact)
+    }
+
+    // The last token in the line. When overlapping it's the one ending last
+    // (the longest if several end together), found by reading the line
+    // backwards. Otherwise it's the last of the tokens taken left to right.
+    pub fn last(&self, line: &[u8], mode: Mode) -> Option<Token> {
+        let exact = self.exact_last(line, mode);
+        if self.max_edits == 0 {
+            return exact;
+        }
+
+        // after every token, even ones skipped when not overlapping
+        let limit = match mode {
+            Mode::Overlapping => exact.as_ref().map_or(0, |token| token.span.end),
+            Mode::NonOverlapping => self.exact_last(line, Mode::Overlapping).map_or(0, |token| token.span.end),
+        };
+        self.last_repair(line, limit).or(exact)
+    }
+
+    fn exact_last(&self, line: &[u8], mode: Mode) -> Option<Token> {
+        match mode {
+            Mode::Overlapping => self.backward.leftmost(line.iter().rev().copied(), &self.lengths).
+                map(|(pattern, from_end)| self.token(pattern, line.len() - from_end - self.lengths[pattern])),
+            Mode::NonOverlapping => {
+                let mut last = None;
+                let mut from = 0;
+                while let Some(token) = self.find_at(line, from) {
+                    from = token.span.end;
+                    last = Some(token);
+                }
+                last
+            },
+        }
+    }
+}
+
+// Whether a token can start or end at i in line without splitting a
+// letter, i.e. i is the end or isn't on a UTF-8 continuation byte.
+fn is_char_boundary(line: &[u8], i: usize) -> bool {
+    line.get(i).is_none_or(|&b| b & 0xc0 != 0x80)
+}
+
+// The letters of s, which has fewer than EDIT_WIDTH of them, and how many
+// there are.
+fn letters(s: &str) -> ([char; EDIT_WIDTH], usize) {
+    let mut letters = ['\0'; EDIT_WIDTH];
+    let mut len = 0;
+    for c in s.chars() {
+        letters[len] = c;
+        len += 1;
+    }
+    (letters, len)
+}
+
+// The fewest edits to turn a into b, counting swapping two letters next to
+// each other as one, which both have to be at most MAX_FUZZY_LEN * 2
+// letters long.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, a_len) = letters(a);
+    let (b, b_len) = letters(b);
+    let mut before = [0; EDIT_WIDTH];
+    let mut previous: [usize; EDIT_WIDTH] = std::array::from_fn(|j| j);
+    let mut current = [0; EDIT_WIDTH];
+
+    for i in 1..=a_len {
+        current[0] = i;
+        for j in 1..=b_len {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            current[j] = (previous[j] + 1).min(current[j - 1] + 1).min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        before = previous;
+        previous = current;
+    }
+
+    previous[b_len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary::ENGLISH;
+
+    fn values(matcher: &Matcher, line: &str, mode: Mode) -> Option<(u32, u32)> {
+        let first = matcher.first(line.as_bytes())?;
+        let last = matcher.last(line.as_bytes(), mode)?;
+        Some((first.value, last.value))
+    }
+
+    #[test]
+    fn test_matcher() {
+        let matcher = Matcher::new(&ENGLISH);
+        assert_eq!(values(&matcher, "eightwo", Mode::Overlapping), Some((8, 2)));
+        assert_eq!(values(&matcher, "eightwo", Mode::NonOverlapping), Some((8, 8)));
+        assert_eq!(values(&matcher, "xtwone3four", Mode::Overlapping), Some((2, 4)));
+        assert_eq!(values(&matcher, "zoneight234", Mode::NonOverlapping), Some((1, 4)));
+        assert_eq!(values(&matcher, "oneightwone", Mode::NonOverlapping), Some((1, 2)));
+        assert_eq!(values(&matcher, "oneightwone", Mode::Overlapping), Some((1, 1)));
+        assert_eq!(values(&matcher, "nothing here", Mode::Overlapping), None);
+
+        let first = matcher.first(b"abcone2threexyz").unwrap();
+        assert_eq!(first, Token { value: 1, span: 3..6, kind: Kind::Word });
+        let last = matcher.last(b"abcone2threexyz", Mode::Overlapping).unwrap();
+        assert_eq!(last, Token { value: 3, span: 7..12, kind: Kind::Word });
+        assert_eq!(matcher.find_at(b"abcone2threexyz", 4).unwrap().span, 6..7);
+
+        // digits alone don't see the words
+        let digits = Matcher::digits();
+        assert_eq!(values(&digits, "two1nine", Mode::Overlapping), Some((1, 1)));
+        assert_eq!(digits.last(b"a0b", Mode::Overlapping).unwrap(), Token { value: 0, span: 1..2, kind: Kind::Digit });
+    }
+
+    #[test]
+    fn test_prefixes() {
+        // words inside, or at the start of, other words
+        let matcher = Matcher::new(&[("ab", 1), ("abcd", 2), ("bc", 3), ("cde", 4)]);
+        assert_eq!(matcher.first(b"xabcde").unwrap().span, 1..5);
+        assert_eq!(matcher.last(b"xabcde", Mode::Overlapping).unwrap(), Token { value: 4, span: 3..6, kind: Kind::Word });
+        assert_eq!(matcher.last(b"xabcdx", Mode::Overlapping).unwrap().value, 2);
+        assert_eq!(matcher.last(b"xabcx", Mode::Overlapping).unwrap().value, 3);
+        assert_eq!(matcher.last(b"xabcde", Mode::NonOverlapping).unwrap().value, 2);
+        assert_eq!(matcher.first(b"xabx").unwrap().value, 1);
+    }
+
+    #[test]
+    fn test_fuzzy() {
+        assert_eq!(edit_distance("seveen", "seven"), 1);
+        assert_eq!(edit_distance("nien", "nine"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "four"), 4);
+        assert_eq!(edit_distance("fünf", "funf"), 1);
+        assert_eq!(edit_distance("nüf", "fün"), 2);
+
+        let matcher = Matcher::new(&ENGLISH).fuzzy(1);
+        assert_eq!(matcher.first(b"xseveen2nien"), Some(Token { value: 7, span: 1..7, kind: Kind::Repaired { word: 6, edits: 1 } }));
+        assert_eq!(matcher.last(b"xseveen2nien", Mode::Overlapping), Some(Token { value: 9, span: 8..12, kind: Kind::Repaired { word: 8, edits: 1 } }));
+        // short words aren't guessed at, and exact ones win
+        assert_eq!(values(&matcher, "onx3twp", Mode::Overlapping), Some((3, 3)));
+        assert_eq!(values(&matcher, "sevenine", Mode::Overlapping), Some((7, 9)));
+        assert_eq!(values(&matcher, "eightwo", Mode::NonOverlapping), Some((8, 8)));
+        // only when asked for
+        assert_eq!(Matcher::new(&ENGLISH).first(b"nien"), None);
+        assert_eq!(values(&matcher, "thre", Mode::Overlapping), Some((3, 3)));
+        assert_eq!(values(&Matcher::new(&ENGLISH).fuzzy(2), "fivve", Mode::Overlapping), Some((5, 5)));
+    }
+
+    #[test]
+    fn test_fuzzy_letters() {
+        // spans only start and end between letters, and "ü" is one of them
+        let german = Matcher::new(&crate::vocabulary::GERMAN).fuzzy(1);
+        let repaired = |span, word| Some(Token { value: 5, span, kind: Kind::Repaired { word, edits: 1 } });
+        assert_eq!(german.first("fünü".as_bytes()), repaired(0..6, 6));
+        assert_eq!(german.last("fünü".as_bytes(), Mode::Overlapping), repaired(0..6, 6));
+        assert_eq!(german.first("xfümfx".as_bytes()), repaired(1..6, 6));
+        assert_eq!(german.last("3fuenff".as_bytes(), Mode::Overlapping).unwrap().value, 5);
+
+        let french = Matcher::new(&crate::vocabulary::FRENCH).fuzzy(1);
+        assert_eq!(french.first("zérro".as_bytes()).unwrap().span, 0..6);
+        // and a line that isn't UTF-8 has no misspellings in it
+        assert_eq!(german.first(b"f\xfcnf"), None);
+    }
+}
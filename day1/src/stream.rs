@@ -0,0 +1,76 @@
+// Solving both parts while reading the document, for calibration files too
+// big to hold in memory. Each line is read into the same buffer and looked
+// at from both ends, stopping at the first token found from each, so a line
+// is mostly only read once and nothing is allocated for it.
+use std::io::{self, BufRead};
+
+use crate::Params;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub part1: u64,
+    pub part2: u64,
+    pub lines: u64,
+    pub bytes: u64,
+}
+
+// The first and last digit, as part 1 reads them.
+fn digits_value(line: &[u8]) -> u64 {
+    let first = line.iter().find(|b| b.is_ascii_digit());
+    let last = line.iter().rev().find(|b| b.is_ascii_digit());
+
+    match (first, last) {
+        (Some(first), Some(last)) => ((first - b'0') * 10 + (last - b'0')) as u64,
+        _ => 0,
+    }
+}
+
+pub fn solve(mut reader: impl BufRead, params: &Params) -> io::Result<Totals> {
+    let matcher = params.matcher();
+    let mut totals = Totals::default();
+    let mut buffer = Vec::new();
+
+    loop {
+        buffer.clear();
+        let read = reader.read_until(b'\n', &mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        totals.bytes += read as u64;
+        totals.lines += 1;
+
+        let mut line = buffer.as_slice();
+        if let Some(rest) = line.strip_suffix(b"\n") {
+            line = rest.strip_suffix(b"\r").unwrap_or(rest);
+        }
+
+        totals.part1 += digits_value(line);
+        if let (Some(first), Some(last)) = (matcher.first(line), matcher.last(line, params.mode)) {
+            totals.part2 += (first.value * 10 + last.value) as u64;
+        }
+    }
+
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::Mode;
+
+    #[test]
+    fn test_solve() {
+        let text = "two1nine\r\neightwothree\nabcone2threexyz\n\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
+        let input = crate::parse(text).unwrap();
+
+        let totals = solve(text.as_bytes(), &Params::default()).unwrap();
+        assert_eq!(totals, Totals { part1: crate::part1(&input), part2: 281, lines: 8, bytes: text.len() as u64 });
+
+        let params = Params { mode: Mode::NonOverlapping, ..Params::default() };
+        assert_eq!(solve(text.as_bytes(), &params).unwrap().part2, crate::part2_with(&input, &params));
+
+        // reading a line at a time through a small buffer changes nothing
+        let small = io::BufReader::with_capacity(4, text.as_bytes());
+        assert_eq!(solve(small, &Params::default()).unwrap(), totals);
+    }
+}
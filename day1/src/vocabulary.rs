@@ -0,0 +1,150 @@
+// The number words part 2 looks for besides the digits. There are packs for
+// a few languages, or a list can be read from a file.
+use std::fs;
+use std::path::Path;
+
+// The puzzle's words.
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5),
+    ("six", 6), ("seven", 7), ("eight", 8), ("nine", 9),
+];
+
+pub const GERMAN: [(&str, u32); 12] = [
+    ("null", 0), ("eins", 1), ("zwei", 2), ("zwo", 2), ("drei", 3), ("vier", 4),
+    ("fünf", 5), ("fuenf", 5), ("sechs", 6), ("sieben", 7), ("acht", 8), ("neun", 9),
+];
+
+pub const FRENCH: [(&str, u32); 12] = [
+    ("zéro", 0), ("zero", 0), ("un", 1), ("une", 1), ("deux", 2), ("trois", 3),
+    ("quatre", 4), ("cinq", 5), ("six", 6), ("sept", 7), ("huit", 8), ("neuf", 9),
+];
+
+pub const SPANISH: [(&str, u32); 11] = [
+    ("cero", 0), ("un", 1), ("uno", 1), ("dos", 2), ("tres", 3), ("cuatro", 4),
+    ("cinco", 5), ("seis", 6), ("siete", 7), ("ocho", 8), ("nueve", 9),
+];
+
+pub const PACKS: [&str; 5] = ["english", "english-zero", "german", "french", "spanish"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u32)>,
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Vocabulary::from_words(&ENGLISH)
+    }
+}
+
+impl Vocabulary {
+    fn from_words(words: &[(&str, u32)]) -> Vocabulary {
+        Vocabulary { words: words.iter().map(|(word, value)| (word.to_string(), *value)).collect() }
+    }
+
+    // One of the PACKS.
+    pub fn pack(name: &str) -> Option<Vocabulary> {
+        match name {
+            "english" => Some(Vocabulary::from_words(&ENGLISH)),
+            "english-zero" => {
+                let mut vocabulary = Vocabulary::from_words(&ENGLISH);
+                vocabulary.words.insert(0, ("zero".to_string(), 0));
+                Some(vocabulary)
+            },
+            "german" => Some(Vocabulary::from_words(&GERMAN)),
+            "french" => Some(Vocabulary::from_words(&FRENCH)),
+            "spanish" => Some(Vocabulary::from_words(&SPANISH)),
+            _ => None,
+        }
+    }
+
+    // A word list with a word and its digit on each line, e.g. "drie 3".
+    // Blank lines and anything after a # are ignored. A word can be listed
+    // more than once, but always with the same digit.
+    pub fn parse(text: &str) -> Result<Vocabulary, String> {
+        let mut words: Vec<(String, u32)> = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [word, digit] = fields[..] else {
+                return Err(format!("line {}: expected a word and a digit, not '{}'", i + 1, line));
+            };
+            let Some(value) = digit.parse::<char>().ok().and_then(|c| c.to_digit(10)) else {
+                return Err(format!("line {}: '{}' isn't a digit", i + 1, digit));
+            };
+
+            match words.iter().find(|(known, _)| known == word) {
+                Some((_, known)) if *known != value =>
+                    return Err(format!("line {}: '{}' is already {}", i + 1, word, known)),
+                Some(_) => {},
+                None => words.push((word.to_string(), value)),
+            }
+        }
+
+        if words.is_empty() {
+            return Err("no words in the list".to_string());
+        }
+        Ok(Vocabulary { words })
+    }
+
+    pub fn load(path: &Path) -> Result<Vocabulary, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        Vocabulary::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::{Matcher, Mode};
+
+    fn values(vocabulary: &Vocabulary, line: &str) -> (u32, u32) {
+        let matcher = Matcher::new(&vocabulary.words);
+        let first = matcher.first(line.as_bytes()).unwrap();
+        let last = matcher.last(line.as_bytes(), Mode::Overlapping).unwrap();
+        (first.value, last.value)
+    }
+
+    #[test]
+    fn test_packs() {
+        let pack = |name| Vocabulary::pack(name).unwrap();
+        assert_eq!(pack("english"), Vocabulary::default());
+        assert_eq!(values(&pack("english-zero"), "zeroneight"), (0, 8));
+        assert_eq!(values(&pack("german"), "xfünfzwölfzwo"), (5, 2));
+        assert_eq!(values(&pack("german"), "achtzehneun"), (8, 9));
+        // "une" over the "un" it starts with
+        assert_eq!(Matcher::new(&pack("french").words).first(b"xunex").unwrap().span, 1..4);
+        assert_eq!(values(&pack("french"), "septroisix"), (7, 6));
+        assert_eq!(values(&pack("spanish"), "dosunocho"), (2, 8));
+        assert!(Vocabulary::pack("klingon").is_none());
+        for name in PACKS {
+            assert!(Vocabulary::pack(name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse("# Dutch\nnul 0\neen 1\n\ntwee 2  # two\neen 1\n").unwrap();
+        assert_eq!(vocabulary.words, [("nul".to_string(), 0), ("een".to_string(), 1), ("twee".to_string(), 2)]);
+        assert_eq!(values(&vocabulary, "xeentweex"), (1, 2));
+
+        assert_eq!(Vocabulary::parse("een 1\neen 2\n"), Err("line 2: 'een' is already 1".to_string()));
+        assert!(Vocabulary::parse("een\n").is_err());
+        assert!(Vocabulary::parse("een 10\n").is_err());
+        assert!(Vocabulary::parse("# nothing\n").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join(format!("day1-vocabulary-{}.txt", std::process::id()));
+        fs::write(&path, "een 1\neen 2\n").unwrap();
+        let loaded = Vocabulary::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, Err(format!("{}: line 2: 'een' is already 1", path.display())));
+    }
+}
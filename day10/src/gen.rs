@@ -0,0 +1,157 @@
+// Random but valid inputs for property tests.
+use proptest::prelude::*;
+
+use grid::Grid;
+
+// A maze with a single loop, along with the answers it should give.
+#[derive(Debug, Clone)]
+pub struct LoopMaze {
+    pub text: String,
+    // the number of tiles in the loop
+    pub loop_len: usize,
+    // the number of tiles enclosed by the loop
+    pub inside: usize,
+}
+
+// The pipe joining the given neighbours (up, down, left, right).
+fn pipe(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (true, true, false, false) => '|',
+        (false, false, true, true) => '-',
+        (true, false, false, true) => 'L',
+        (true, false, true, false) => 'J',
+        (false, true, true, false) => '7',
+        (false, true, false, true) => 'F',
+        _ => panic!("not a pipe"),
+    }
+}
+
+// The loop goes around the outline of a blob of cells, with a pipe at each
+// corner of a cell. Returns which neighbours each corner connects to, or
+// None unless the outline is a single loop that never touches itself.
+fn outline(cells: &Grid<bool>) -> Option<Grid<[bool; 4]>> {
+    let mut corners = Grid::filled_with([false; 4], cells.num_rows() + 1, cells.num_columns() + 1);
+    let filled = |r: isize, c: isize| r >= 0 && c >= 0 && cells.get((r as usize, c as usize)) == Some(&true);
+
+    for ((r, c), _) in cells.iter().filter(|(_, filled)| **filled) {
+        let (ri, ci) = (r as isize, c as isize);
+        // [up, down, left, right] for each end of each side with nothing
+        // on the other side of it
+        if !filled(ri - 1, ci) {
+            corners[(r, c)][3] = true;
+            corners[(r, c + 1)][2] = true;
+        }
+        if !filled(ri + 1, ci) {
+            corners[(r + 1, c)][3] = true;
+            corners[(r + 1, c + 1)][2] = true;
+        }
+        if !filled(ri, ci - 1) {
+            corners[(r, c)][1] = true;
+            corners[(r + 1, c)][0] = true;
+        }
+        if !filled(ri, ci + 1) {
+            corners[(r, c + 1)][1] = true;
+            corners[(r + 1, c + 1)][0] = true;
+        }
+    }
+
+    // every corner on the outline must join exactly two sides...
+    let on_loop: Vec<(usize, usize)> = corners.iter().
+        filter(|(_, sides)| sides.iter().any(|s| *s)).
+        map(|(pos, _)| pos).
+        collect();
+    if on_loop.iter().any(|&pos| corners[pos].iter().filter(|s| **s).count() != 2) {
+        return None;
+    }
+
+    // ...and following them from any one must visit them all
+    let start = on_loop[0];
+    let (mut prev, mut pos) = (start, start);
+    let mut len = 0;
+    loop {
+        let [up, down, left, right] = corners[pos];
+        let next = [(up, (pos.0.wrapping_sub(1), pos.1)), (down, (pos.0 + 1, pos.1)),
+                    (left, (pos.0, pos.1.wrapping_sub(1))), (right, (pos.0, pos.1 + 1))].
+            into_iter().
+            find(|&(joined, next)| joined && next != prev).
+            map(|(_, next)| next).unwrap();
+        (prev, pos) = (pos, next);
+        len += 1;
+        if pos == start {
+            break;
+        }
+    }
+
+    (len == on_loop.len()).then_some(corners)
+}
+
+// Grow a blob from one cell, adding a neighbouring cell for each choice as
+// long as its outline stays a single loop.
+fn blob(rows: usize, cols: usize, choices: &[usize]) -> Grid<bool> {
+    let mut cells = Grid::filled_with(false, rows, cols);
+    cells[(choices[0] / cols % rows, choices[0] % cols)] = true;
+
+    for choice in &choices[1..] {
+        let candidates: Vec<(usize, usize)> = cells.positions().
+            filter(|&pos| !cells[pos] && cells.neighbors4(pos).any(|n| cells[n])).
+            collect();
+        if candidates.is_empty() {
+            break;
+        }
+
+        let pos = candidates[choice % candidates.len()];
+        cells[pos] = true;
+        if outline(&cells).is_none() {
+            cells[pos] = false;
+        }
+    }
+
+    cells
+}
+
+// A single-loop maze of up to 11x11 tiles. The tiles that aren't part of
+// the loop are random, except that none of them connect to S, so S has
+// exactly two ways out.
+pub fn maze() -> impl Strategy<Value = LoopMaze> {
+    let choices = prop::collection::vec(0usize..1000, 1..40);
+    let junk = prop::collection::vec(prop::sample::select("|-LJ7F.".chars().collect::<Vec<_>>()), 121);
+
+    (1usize..=8, 1usize..=8, choices, junk, 0usize..1000, 0usize..=1, 0usize..=1).
+        prop_map(|(rows, cols, choices, junk, start, top, left)| {
+            let cells = blob(rows, cols, &choices);
+            let corners = outline(&cells).unwrap();
+            let area = cells.iter().filter(|(_, filled)| **filled).count();
+
+            // leave room for some junk above and to the left, and the same
+            // below and to the right
+            let mut tiles = Grid::from_fn(corners.num_rows() + 2 * top, corners.num_columns() + 2 * left,
+                                          |(r, c)| junk[(r * 11 + c) % junk.len()]);
+            let mut on_loop = Vec::new();
+            for ((r, c), sides) in corners.iter() {
+                if sides.iter().any(|s| *s) {
+                    let [up, down, left_side, right] = *sides;
+                    tiles[(r + top, c + left)] = pipe(up, down, left_side, right);
+                    on_loop.push((r + top, c + left));
+                }
+            }
+
+            let start = on_loop[start % on_loop.len()];
+            tiles[start] = 'S';
+            for dir in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                if let Some(pos) = tiles.offset(start, dir) {
+                    let points_back = match dir {
+                        (-1, 0) => "|7F",
+                        (1, 0) => "|LJ",
+                        (0, -1) => "-LF",
+                        _ => "-J7",
+                    };
+                    if !on_loop.contains(&pos) && points_back.contains(tiles[pos]) {
+                        tiles[pos] = '.';
+                    }
+                }
+            }
+
+            // Pick's theorem: the area is inside + loop / 2 - 1
+            LoopMaze { text: tiles.to_string(), loop_len: on_loop.len(), inside: area + 1 - on_loop.len() / 2 }
+        })
+}
@@ -0,0 +1,498 @@
+use common::{frames, parse, ParseError};
+use log::{info, trace};
+use grid::Grid;
+
+pub const DAY: u32 = 10;
+
+#[cfg(test)]
+mod gen;
+
+// The maze of pipes, the starting position that was marked with 'S', and
+// the pipe that S stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Maze {
+    pub tiles : Grid<char>,
+    pub start_pos : (usize, usize),
+    pub start_pipe : char,
+}
+
+pub type Input = Maze;
+pub type Answer = usize;
+
+// An error pointing at the tile at pos.
+fn tile_error(contents : &str, pos : (usize, usize), message : &str) -> ParseError {
+    let line = parse::lines(DAY, contents).nth(pos.0).unwrap();
+    line.error(&line.text[pos.1..pos.1 + 1], message)
+}
+
+// Load a maze into a 2-d matrix. Also return the starting position
+// that was marked with 'S', of which there must be exactly one. Ground is
+// usually '.', but the annotated examples also mark it 'I' or 'O' for
+// inside and outside the loop. S has to connect to exactly two pipes, and
+// following them has to lead round a loop back to S, so the solvers can
+// walk the loop without checking.
+pub fn parse(contents : &str) -> Result<Input, ParseError> {
+    let maze = parse::grid(DAY, contents, |c| "|-LJ7F.IOS".contains(c).then_some(c))?;
+    let starts : Vec<(usize, usize)> = maze.iter().filter(|(_, c)| **c == 'S').map(|(pos, _)| pos).collect();
+
+    let Some(&start_pos) = starts.first() else {
+        return Err(ParseError::at_end(DAY, contents, "expected a starting position marked with 'S'"));
+    };
+    if let Some(&pos) = starts.get(1) {
+        return Err(tile_error(contents, pos, "found more than one starting position"));
+    }
+
+    let ways_out : Vec<Direction> = ALL_DIRECTIONS.into_iter().
+        filter(|dir| maze.offset(start_pos, dir.delta()).is_some_and(|pos| dir.connectors().contains(&maze[pos]))).
+        collect();
+    let [dir_1, dir_2] = ways_out[..] else {
+        return Err(tile_error(contents, start_pos, &format!("expected S to connect to 2 pipes, found {}", ways_out.len())));
+    };
+
+    // follow the pipes from S until they come back to it
+    let mut pos = start_pos;
+    let mut dir = dir_1;
+    loop {
+        let Some(next_pos) = maze.offset(pos, dir.delta()) else {
+            return Err(tile_error(contents, pos, "expected the loop to stay inside the maze"));
+        };
+        if next_pos == start_pos {
+            break;
+        }
+        let Some(next_dir) = dir.turn(maze[next_pos]) else {
+            return Err(tile_error(contents, next_pos, "expected the loop to carry on through this tile"));
+        };
+        pos = next_pos;
+        dir = next_dir;
+    }
+
+    Ok(Maze { tiles: maze, start_pos, start_pipe: pipe_between(dir_1, dir_2) })
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+const ALL_DIRECTIONS : [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+impl Direction {
+    // (row, col) offset of one step in this direction
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up    => (-1, 0),
+            Direction::Down  => (1, 0),
+            Direction::Left  => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    // pipe characters that connect back to us when we step in this direction
+    fn connectors(self) -> [char; 3] {
+        match self {
+            Direction::Up    => ['|', 'F', '7'],
+            Direction::Down  => ['|', 'J', 'L'],
+            Direction::Left  => ['-', 'F', 'L'],
+            Direction::Right => ['-', 'J', '7'],
+        }
+    }
+
+    // which way we leave a pipe we stepped onto going this way, or None if
+    // it doesn't connect back to us
+    fn turn(self, pipe : char) -> Option<Direction> {
+        match (self, pipe) {
+            (Direction::Up, '|') | (Direction::Left, 'L') | (Direction::Right, 'J') => Some(Direction::Up),
+            (Direction::Down, '|') | (Direction::Left, 'F') | (Direction::Right, '7') => Some(Direction::Down),
+            (Direction::Left, '-') | (Direction::Up, '7') | (Direction::Down, 'J') => Some(Direction::Left),
+            (Direction::Right, '-') | (Direction::Up, 'F') | (Direction::Down, 'L') => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+// The pipe with ways out in directions a and b, which parse has made sure
+// are two different ones.
+fn pipe_between(a : Direction, b : Direction) -> char {
+    let has = |dir| a == dir || b == dir;
+
+    match (has(Direction::Up), has(Direction::Down), has(Direction::Left)) {
+        (true, true, _) => '|',
+        (true, _, true) => 'J',
+        (true, _, _) => 'L',
+        (_, true, true) => '7',
+        (_, true, _) => 'F',
+        _ => '-',
+    }
+}
+
+// Given a maze, a starting position, and a starting direction, fill in the distances to all other reachable points
+fn fill_dists(maze : &Grid<char>, start_pos : (usize, usize), start_dir : Direction, dist : &mut Grid<usize>) {
+    let mut cur_pos = start_pos;
+    let mut this_move = start_dir;
+
+    dist[start_pos] = 0;
+
+    // parse checked that the loop closes, so this only stops back at S,
+    // which no way of stepping onto turns through
+    while let Some(next_pos) = maze.offset(cur_pos, this_move.delta()) {
+        let Some(next_move) = this_move.turn(maze[next_pos]) else {
+            break;
+        };
+
+        if dist[next_pos] != usize::MAX {
+            // we've already been here, so we're done
+            break;
+        }
+
+        dist[next_pos] = dist[cur_pos] + 1;
+        cur_pos = next_pos;
+        this_move = next_move;
+    }
+}
+
+// Given a maze and a starting position, figure out which way to go first
+// based on neighboring characters. reverse_search can be used to find the
+// other way out of the start.
+fn determine_initial_direction(maze : &Grid<char>, start_pos : (usize, usize), reverse_search : bool) -> Direction {
+    // the reverse search has to check directions in exactly the opposite
+    // order, otherwise both searches can pick the same way out of the start.
+    let mut search_order : Vec<Direction> = vec![Direction::Down, Direction::Left, Direction::Up, Direction::Right];
+    if reverse_search {
+        search_order.reverse();
+    }
+
+    for dir in search_order {
+        trace!("Checking dir: {:?} from cur_pos1 {:?}", dir, start_pos);
+        if let Some(pos) = maze.offset(start_pos, dir.delta()) {
+            if dir.connectors().contains(&maze[pos]) {
+                return dir;
+            }
+        }
+    }
+
+    Direction::Up
+}
+
+fn print_maze(label : &str, maze : &Grid<char>) {
+    trace!("{}:\n{}", label, maze);
+    frames::snapshot(label, || maze.to_string());
+}
+
+// How many snapshots of the flood fill to record, roughly.
+const FILL_FRAMES : usize = 40;
+
+// Flood fill algorithm to fill in all the 'o' characters we can reach.
+// It keeps a stack of the cells still to look at rather than recursing,
+// which would run out of stack on the expanded map of a real input. The
+// map is recorded every so many cells.
+fn flood_fill (maze : &mut Grid<char>, start : (usize, usize), every : usize) {
+    let mut to_visit = vec![start];
+    let mut filled = 0;
+
+    while let Some(node) = to_visit.pop() {
+        if maze[node] == '?' {
+            maze[node] = 'O';
+        } else if maze[node] == 'o' {
+            maze[node] = '.';   // mark as visited
+            filled += 1;
+            if filled % every == 0 {
+                frames::snapshot(&format!("Filling: {} reached", filled), || maze.to_string());
+            }
+
+            to_visit.extend(maze.neighbors4(node));
+        }
+    }
+}
+
+// iterate through maze from start_pos, traversing both directions away from the start and looping back to the start.
+// We'll fill in both dist_1 and dist_2 with the distances to all reachable points, where they have the same value
+// for the same point, that's the intersection and the furthest point.
+fn loop_dists(maze : &Maze) -> (Grid<usize>, Grid<usize>) {
+    let maze_size = (maze.tiles.num_rows(), maze.tiles.num_columns());
+    let mut dist_1: Grid<usize> = Grid::filled_with(usize::MAX, maze_size.0, maze_size.1);
+    let mut dist_2: Grid<usize> = Grid::filled_with(usize::MAX, maze_size.0, maze_size.1);
+
+    // first, fill in dist_1
+    let start_dir_1 = determine_initial_direction(&maze.tiles, maze.start_pos, false);
+    fill_dists(&maze.tiles, maze.start_pos, start_dir_1, &mut dist_1);
+
+    // now, fill in dist_2
+    let start_dir_2 = determine_initial_direction(&maze.tiles, maze.start_pos, true);
+    info!("Start at {:?}, leaving {:?} and {:?}", maze.start_pos, start_dir_1, start_dir_2);
+    fill_dists(&maze.tiles, maze.start_pos, start_dir_2, &mut dist_2);
+
+    (dist_1, dist_2)
+}
+
+pub fn part1(maze : &Input) -> Answer {
+    let maze_size = (maze.tiles.num_rows(), maze.tiles.num_columns());
+    let (dist_1, dist_2) = loop_dists(maze);
+
+    // find intersection
+    let mut max_dist = 0;
+
+    'outer: for i in 0..maze_size.0 {
+        for j in 0..maze_size.1 {
+            if dist_1[(i, j)] == dist_2[(i, j)] && dist_1[(i, j)] != 0 && dist_1[(i, j)] != usize::MAX {
+                max_dist = dist_1[(i, j)];
+                break 'outer;
+            }
+        }
+    }
+
+    max_dist
+}
+
+pub fn part2(input : &Input) -> Answer {
+    let maze = &input.tiles;
+    let maze_size = (maze.num_rows(), maze.num_columns());
+    let (dist_1, _) = loop_dists(input);
+
+    // we'll visualize by marking parts of the loop with the maze, then fill in everything else as 'O'
+    let mut area: Grid<char> = Grid::filled_with('O', maze_size.0, maze_size.1);
+
+    for i in 0..maze_size.0 {
+        for j in 0..maze_size.1 {
+            if dist_1[(i, j)] == usize::MAX {
+                area[(i, j)] = 'O';
+            } else {
+                area[(i, j)] = maze[(i, j)];
+            }
+        }
+    }
+
+    // S is really one of the pipes. Expanding it as anything else leaves
+    // stubs that can wall off the corner the fill starts from.
+    area[input.start_pos] = input.start_pipe;
+
+    print_maze("Original loop", &area);
+
+    // we're going to expand the map now to allow for a fill algorithm that reaches "inside the cracks".
+    let areax_size = (maze_size.0 * 3, maze_size.1 * 3);
+    let mut areax: Grid<char> = Grid::filled_with('O', areax_size.0 , areax_size.1);
+    
+    for i in 0..maze_size.0 {
+        for j in 0..maze_size.1 {
+            match area[(i, j)] {
+                'F' => {
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = 'o';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = 'o';
+                    areax[(i * 3 + 1, j * 3 + 1)] = 'F';
+                    areax[(i * 3 + 1, j * 3 + 2)] = '-';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = '|';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+                'J' => {
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = '|';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = '-';
+                    areax[(i * 3 + 1, j * 3 + 1)] = 'J';
+                    areax[(i * 3 + 1, j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = 'o';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+                'L' => {
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = '|';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = 'o';
+                    areax[(i * 3 + 1, j * 3 + 1)] = 'L';
+                    areax[(i * 3 + 1, j * 3 + 2)] = '-';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = 'o';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+                '|' => {
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = '|';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = 'o';
+                    areax[(i * 3 + 1, j * 3 + 1)] = '|';
+                    areax[(i * 3 + 1, j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = '|';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+                '-' => {
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = 'o';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = '-';
+                    areax[(i * 3 + 1, j * 3 + 1)] = '-';
+                    areax[(i * 3 + 1, j * 3 + 2)] = '-';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = 'o';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+                '7' => {
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = 'o';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = '-';
+                    areax[(i * 3 + 1, j * 3 + 1)] = '7';
+                    areax[(i * 3 + 1, j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = '|';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+                _ => {
+                    // off the loop ('O'). row, col
+                    areax[(i * 3,     j * 3)]     = 'o';
+                    areax[(i * 3,     j * 3 + 1)] = 'o';
+                    areax[(i * 3,     j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 1, j * 3)]     = 'o';
+                    areax[(i * 3 + 1, j * 3 + 1)] = '?';
+                    areax[(i * 3 + 1, j * 3 + 2)] = 'o';
+                    areax[(i * 3 + 2, j * 3)]     = 'o';
+                    areax[(i * 3 + 2, j * 3 + 1)] = 'o';
+                    areax[(i * 3 + 2, j * 3 + 2)] = 'o';
+                },
+            }
+        }
+    }
+    
+    print_maze("Expanded loop map", &areax);
+
+    // Now run a fill algorithm. We're going to identify all the 'o' characters we can reach.
+    // If we can reach a '?' then we'll replace it with an 'O' to represent an original O outside
+    // of the map. The remaining number of question marks were other non-loop empty spaces,
+    // and therefore are the inside area.
+    let every = (areax_size.0 * areax_size.1 / FILL_FRAMES).max(1);
+    flood_fill(&mut areax, (0, 0), every);
+
+    print_maze("Filled loop map", &areax);
+
+    let mut area_count = 0;
+    for i in 0..areax_size.0 {
+        for j in 0..areax_size.1 {
+            if areax[(i, j)] == '?' {
+                area_count += 1;
+            }
+        }
+    }
+
+    area_count
+}
+
+#[cfg(test)]
+mod tests {
+    use common::differential;
+    use common::input::Source;
+    use proptest::prelude::*;
+
+    use crate::gen;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 8);
+        assert_eq!(super::part1(&example("input_test2.txt")), 23);
+        assert_eq!(super::part1(&example("input_test2b.txt")), 22);
+        assert_eq!(super::part1(&example("input_test3.txt")), 70);
+        assert_eq!(super::part1(&example("input_test4.txt")), 80);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 1);
+        assert_eq!(super::part2(&example("input_test2.txt")), 4);
+        assert_eq!(super::part2(&example("input_test2b.txt")), 4);
+        assert_eq!(super::part2(&example("input_test3.txt")), 8);
+        assert_eq!(super::part2(&example("input_test4.txt")), 10);
+    }
+
+    // S in the top left corner, where the fill starts, has to be expanded
+    // as the F it stands for, without stubs walling that corner off
+    #[test]
+    fn test_start_in_corner() {
+        let maze = super::parse("S-7\n|FJ\n|L7\nL-J\n|||\n").unwrap();
+        assert_eq!(maze.start_pipe, 'F');
+        assert_eq!(super::part2(&maze), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = super::parse("S-7\n|X|\nL-J\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "X"));
+
+        let e = super::parse("S-7\n|.|\nL-S\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (3, 3, "found more than one starting position"));
+
+        assert!(super::parse("F-7\n|.|\nL-J\n").is_err());
+
+        // S has to be on a loop that closes
+        let e = super::parse("S.\n..\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (1, 1, "expected S to connect to 2 pipes, found 0"));
+        let e = super::parse(".S.\n.|.\n...\n").unwrap_err();
+        assert_eq!(e.message, "expected S to connect to 2 pipes, found 1");
+        let e = super::parse("S-7\n|.|\nL-.\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (3, 3, "expected the loop to carry on through this tile"));
+        let e = super::parse("S-7\n|.|\nL-L\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+        let e = super::parse("S-\n|.\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 1, "expected the loop to stay inside the maze"));
+    }
+
+    proptest! {
+        // the furthest point is half way round the loop, and the tiles
+        // inside agree with the loop's area
+        #[test]
+        fn test_generated_loop(maze in gen::maze()) {
+            let parsed = super::parse(&maze.text).unwrap();
+            prop_assert_eq!(super::part1(&parsed), maze.loop_len / 2);
+            prop_assert_eq!(super::part2(&parsed), maze.inside);
+        }
+    }
+
+    // Count the tiles inside the loop without expanding the map: scanning
+    // along a row, we're inside after crossing the loop an odd number of
+    // times. Only pipes that go up count as a crossing, so running along the
+    // loop (e.g. "L--7" or "F--7") counts correctly.
+    fn inside_by_crossings(maze: &super::Maze) -> usize {
+        let (dist, _) = super::loop_dists(maze);
+        let mut tiles = maze.tiles.clone();
+        tiles[maze.start_pos] = maze.start_pipe;
+
+        let mut count = 0;
+        for (row, cells) in tiles.rows_iter().enumerate() {
+            let mut inside = false;
+            for (col, tile) in cells.iter().enumerate() {
+                if dist[(row, col)] != usize::MAX {
+                    if "|LJ".contains(*tile) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    #[test]
+    fn test_examples_crossings() {
+        for filename in ["input_test.txt", "input_test2.txt", "input_test3.txt", "input_test4.txt"] {
+            let maze = example(filename);
+            assert_eq!(inside_by_crossings(&maze), super::part2(&maze), "{}", filename);
+        }
+    }
+
+    differential!(test_part2_crossings, gen::maze().prop_map(|maze| super::parse(&maze.text).unwrap()),
+                  inside_by_crossings, super::part2);
+}
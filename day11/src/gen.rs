@@ -0,0 +1,34 @@
+// Random but valid inputs for property tests.
+use proptest::prelude::*;
+
+// A map of up to 15x15 with roughly one galaxy in six cells.
+pub fn image() -> impl Strategy<Value = Vec<String>> {
+    (1usize..=15, 1usize..=15).prop_flat_map(|(rows, cols)| {
+        let cell = prop_oneof![5 => Just('.'), 1 => Just('#')];
+        let row = prop::collection::vec(cell, cols).prop_map(|row| row.into_iter().collect::<String>());
+        prop::collection::vec(row, rows)
+    })
+}
+
+pub fn to_input(image: &[String]) -> String {
+    image.iter().map(|row| format!("{}\n", row)).collect()
+}
+
+// The image after the universe expands: every row and column without a
+// galaxy appears twice.
+pub fn expand(image: &[String]) -> Vec<String> {
+    let empty_col = |c: usize| image.iter().all(|row| row.as_bytes()[c] == b'.');
+
+    let mut expanded = Vec::new();
+    for row in image {
+        let wide: String = row.chars().enumerate().
+            flat_map(|(c, ch)| std::iter::repeat_n(ch, if empty_col(c) { 2 } else { 1 })).
+            collect();
+        if !row.contains('#') {
+            expanded.push(wide.clone());
+        }
+        expanded.push(wide);
+    }
+
+    expanded
+}
@@ -0,0 +1,172 @@
+use common::{config, parse, ParseError};
+use grid::Grid;
+
+pub const DAY: u32 = 11;
+
+#[cfg(test)]
+mod gen;
+
+pub type Input = Grid<char>;
+pub type Answer = usize;
+
+// Load into a 2-d matrix, sized by the lines of the image
+pub fn parse(contents : &str) -> Result<Input, ParseError> {
+    parse::grid(DAY, contents, |c| (c == '.' || c == '#').then_some(c))
+}
+
+// manhattan distance between two points
+pub fn manhattan_dist(pos1: (usize, usize), pos2: (usize, usize)) -> usize {
+    pos1.0.abs_diff(pos2.0) + pos1.1.abs_diff(pos2.1)
+}
+
+// Sum the distances between every pair of stars, where each empty row and
+// column between them counts as `expansion` rows or columns.
+fn sum_of_dists(gal0: &Input, expansion: usize) -> usize {
+    // Make lists of empty rows and columns.
+    let empty_rows: Vec<bool> = gal0.rows_iter().map(|row| !row.contains(&'#')).collect();
+    let empty_cols: Vec<bool> = gal0.columns_iter().map(|mut col| col.all(|c| *c != '#')).collect();
+
+    // store stars as a list of (row, col) tuples
+    let stars: Vec<(usize, usize)> = gal0.iter().
+        filter(|(_, c)| **c == '#').
+        map(|(pos, _)| pos).
+        collect();
+
+    let mut sum = 0;
+
+    for i in 0..stars.len() {
+        for j in i+1..stars.len() {
+            let star_i = stars[i];
+            let star_j = stars[j];
+
+            // determine number of empty rows and cols between these stars
+            //
+            // N.B. I'm glad I did it this way and not actually expanding the matrix
+            // (my original plan but this seemed easier for part 1 - it paid off for part 2!)
+            let (sr, er) = if star_i.0 < star_j.0 { (star_i.0, star_j.0) } else { (star_j.0, star_i.0) };
+            let (sc, ec) = if star_i.1 < star_j.1 { (star_i.1, star_j.1) } else { (star_j.1, star_i.1) };
+            let empty_rows_between = empty_rows[sr..er].iter().filter(|x| **x).count();
+            let empty_cols_between = empty_cols[sc..ec].iter().filter(|x| **x).count();
+
+            // each empty row/col is already counted once by the manhattan distance
+            let dist = manhattan_dist(star_i, star_j) +
+                                empty_rows_between * (expansion - 1) +
+                                empty_cols_between * (expansion - 1);
+
+            sum += dist;
+        }
+    }
+
+    sum
+}
+
+// How much bigger each empty row and column gets in part 2, which aoc.toml
+// can change as expansion. The puzzle's examples use 10 and 100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub expansion: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { expansion: 1_000_000 }
+    }
+}
+
+impl Params {
+    pub fn from_config(params: &config::Params) -> Result<Params, String> {
+        params.only(&["expansion"])?;
+        let expansion = params.int("expansion", Params::default().expansion)?;
+        if expansion == 0 {
+            return Err("expansion must be at least 1".to_string());
+        }
+
+        Ok(Params { expansion })
+    }
+}
+
+pub fn part1(gal0 : &Input) -> Answer {
+    sum_of_dists(gal0, 2)
+}
+
+// Part 1 always doubles the empty space.
+pub fn part1_with(gal0 : &Input, _params : &Params) -> Answer {
+    part1(gal0)
+}
+
+pub fn part2(gal0 : &Input) -> Answer {
+    part2_with(gal0, &Params::default())
+}
+
+pub fn part2_with(gal0 : &Input, params : &Params) -> Answer {
+    sum_of_dists(gal0, params.expansion)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+    use proptest::prelude::*;
+
+    use crate::gen;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 374);
+    }
+
+    #[test]
+    fn test_part2() {
+        // the puzzle only gives answers for smaller expansions of the example
+        assert_eq!(super::sum_of_dists(&example("input_test.txt"), 10), 1030);
+        assert_eq!(super::sum_of_dists(&example("input_test.txt"), 100), 8410);
+        assert_eq!(super::part2(&example("input_test.txt")), 82000210);
+    }
+
+    #[test]
+    fn test_params() {
+        let config = common::config::Config::parse("[day11]\nexpansion = 100\n").unwrap();
+        let params = super::Params::from_config(&config.day(11).params).unwrap();
+        assert_eq!(super::part2_with(&example("input_test.txt"), &params), 8410);
+
+        let config = common::config::Config::parse("[day11]\nexpansion = 0\nexpand = 2\n").unwrap();
+        assert!(super::Params::from_config(&config.day(11).params).is_err());
+    }
+
+    proptest! {
+        // part 1 is the plain sum of distances in the expanded image
+        #[test]
+        fn test_expanded(image in gen::image()) {
+            let parsed = super::parse(&gen::to_input(&image)).unwrap();
+            let expanded = super::parse(&gen::to_input(&gen::expand(&image))).unwrap();
+            prop_assert_eq!(super::part1(&parsed), super::sum_of_dists(&expanded, 1));
+        }
+
+        // each extra step of expansion adds the same distance, so part 2
+        // follows from the sums with no expansion and with part 1's
+        #[test]
+        fn test_linear(image in gen::image()) {
+            let parsed = super::parse(&gen::to_input(&image)).unwrap();
+            let base = super::sum_of_dists(&parsed, 1);
+            let step = super::part1(&parsed) - base;
+            prop_assert_eq!(super::sum_of_dists(&parsed, 10), base + 9 * step);
+            prop_assert_eq!(super::part2(&parsed), base + 999_999 * step);
+        }
+
+        // turning the image on its side doesn't change any distance
+        #[test]
+        fn test_transpose(image in gen::image()) {
+            let parsed = super::parse(&gen::to_input(&image)).unwrap();
+            let transposed = parsed.transpose();
+            prop_assert_eq!(super::part1(&transposed), super::part1(&parsed));
+            prop_assert_eq!(super::part2(&transposed), super::part2(&parsed));
+        }
+    }
+}
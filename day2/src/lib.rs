@@ -0,0 +1,202 @@
+use common::parse::{self, Line};
+use common::{config, ParseError};
+use log::debug;
+
+pub const DAY: u32 = 2;
+
+// A single "grab" of cubes from the bag, as (red, green, blue) counts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Draw {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: i32,
+    pub draws: Vec<Draw>,
+}
+
+pub type Input = Vec<Game>;
+pub type Answer = i32;
+
+// Parse a single line like "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red"
+fn parse_game(line: &Line) -> Result<Game, ParseError> {
+    // parse game ID, will be after "Game " until colon
+    let (game, rest) = line.split_once(line.text, ":")?;
+    let game_num: i32 = line.number(line.strip_prefix(game, "Game ")?.trim())?;
+
+    let mut draws = Vec::new();
+
+    // check each "draw" of cubes
+    for part in rest.split(';') {
+        let mut draw = Draw::default();
+
+        // split by comma, now two parts, a number and a color
+        for color in part.split(',').map(|l| l.trim()) {
+            let (color_num_str, color_val_str) = line.split_once(color, " ")?;
+            let color_num: i32 = line.number(color_num_str)?;
+
+            match color_val_str {
+                "red" => draw.red = color_num,
+                "green" => draw.green = color_num,
+                "blue" => draw.blue = color_num,
+                _ => return Err(line.error(color_val_str, "expected red, green or blue")),
+            }
+        }
+
+        draws.push(draw);
+    }
+
+    Ok(Game { id: game_num, draws })
+}
+
+// One game per line, blank lines are ignored.
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    parse::lines(DAY, input).
+        filter(|line| !line.is_blank()).
+        map(|line| parse_game(&line)).
+        collect()
+}
+
+// How many cubes of each colour are in the bag for part 1, which aoc.toml
+// can change as max_red, max_green and max_blue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub max_red: i32,
+    pub max_green: i32,
+    pub max_blue: i32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { max_red: 12, max_green: 13, max_blue: 14 }
+    }
+}
+
+impl Params {
+    pub fn from_config(params: &config::Params) -> Result<Params, String> {
+        params.only(&["max_red", "max_green", "max_blue"])?;
+        let default = Params::default();
+
+        Ok(Params {
+            max_red: params.int("max_red", default.max_red)?,
+            max_green: params.int("max_green", default.max_green)?,
+            max_blue: params.int("max_blue", default.max_blue)?,
+        })
+    }
+}
+
+pub fn part1(input: &Input) -> Answer {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &Input, params: &Params) -> Answer {
+    // max number of cubes we can draw per "grab"
+    let max_red: i32 = params.max_red;
+    let max_green: i32 = params.max_green;
+    let max_blue: i32 = params.max_blue;
+
+    // mutable var for running sum
+    let mut sum = 0;
+
+    for game in input {
+        // game is valid until proven otherwise
+        let mut valid_game: bool = true;
+        debug!("Game number {}", game.id);
+
+        for draw in &game.draws {
+            if draw.red > max_red {
+                valid_game = false;
+                debug!("Too many red: {}", draw.red);
+            } else if draw.green > max_green {
+                valid_game = false;
+                debug!("Too many green: {}", draw.green);
+            } else if draw.blue > max_blue {
+                valid_game = false;
+                debug!("Too many blue: {}", draw.blue);
+            }
+
+            if !valid_game {
+                break;
+            }
+        }
+
+        if valid_game {
+            sum += game.id;
+            debug!("Game {} is valid, sum: {}", game.id, sum);
+        } else {
+            debug!("Game {} is invalid.", game.id);
+        }
+    }
+
+    sum
+}
+
+// Part 2 finds the fewest cubes the bag could hold, so it doesn't depend on
+// the parameters.
+pub fn part2_with(input: &Input, _params: &Params) -> Answer {
+    part2(input)
+}
+
+pub fn part2(input: &Input) -> Answer {
+    let mut sum = 0;
+
+    for game in input {
+        // max number of cubes we've seen per "grab"
+        let mut max_red: i32 = 0;
+        let mut max_green: i32 = 0;
+        let mut max_blue: i32 = 0;
+
+        for draw in &game.draws {
+            max_red = max_red.max(draw.red);
+            max_green = max_green.max(draw.green);
+            max_blue = max_blue.max(draw.blue);
+        }
+
+        let game_power = max_red * max_green * max_blue;
+
+        sum += game_power;
+
+        debug!("Game {}, min red/green/blue: {} {} {} power: {} sum: {}", game.id, max_red, max_green, max_blue, game_power, sum);
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 2286);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // a trailing blank line is fine
+        let games = super::parse("Game 1: 3 blue, 4 red; 1 red\n\n").unwrap();
+        assert_eq!(games[0].draws[1], super::Draw { red: 1, green: 0, blue: 0 });
+
+        let e = super::parse("Game 1: 3 blue\nGame 2 4 red").unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 13, "expected ':'"));
+
+        let e = super::parse("Game 1: 3 blue, 4 rod").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 19, "rod"));
+    }
+}
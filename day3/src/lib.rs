@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use common::{parse, ParseError};
+use log::{debug, trace};
+use grid::Grid;
+
+pub const DAY: u32 = 3;
+
+pub type Input = Grid<char>;
+// each number fits in a u32, so their sums and products need more room
+pub type Answer = u64;
+
+// load 2-d matrix. Anything goes except for control characters and
+// whitespace, which would otherwise be mistaken for symbols or blanks.
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let matrix = parse::grid(DAY, input, |c| c.is_ascii_graphic().then_some(c))?;
+
+    // the numbers are read into u32s, so check they all fit
+    for line in parse::lines(DAY, input) {
+        for num_str in line.text.split(|c: char| !c.is_ascii_digit()).filter(|s| !s.is_empty()) {
+            if num_str.parse::<u32>().is_err() {
+                return Err(line.error(num_str, "expected a number that fits in a u32"));
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+// Given a number spanning [start_idx, end_idx) on a row, return the boundary
+// of coords "around" the number as (startrow, endrow, startcol, endcol),
+// clamped to the edges of the matrix.
+fn search_bounds(matrix: &Input, row: usize, start_idx: usize, end_idx: usize) -> (usize, usize, usize, usize) {
+    let startrow = row.saturating_sub(1);
+    let endrow = (row + 2).min(matrix.num_rows());
+    let startcol = start_idx.saturating_sub(1);
+    let endcol = (end_idx + 1).min(matrix.num_columns());
+
+    (startrow, endrow, startcol, endcol)
+}
+
+// Scan the matrix for numbers, calling found() with each number and its
+// position as (row, start_idx, end_idx).
+fn for_each_number(matrix: &Input, mut found: impl FnMut(u32, usize, usize, usize)) {
+    for row in 0..matrix.num_rows() {
+        let mut col = 0;
+        while col < matrix.num_columns() {
+            if matrix[(row, col)].is_ascii_digit() {
+                trace!("Found digit at [{}, {}]", row, col);
+                let start_idx = col;
+                let mut end_idx = col;
+
+                while end_idx < matrix.num_columns() {
+                    if matrix[(row, end_idx)].is_ascii_digit() {
+                        end_idx += 1;
+                    } else {
+                        break;
+                    }
+                }
+
+                let num_str: String = matrix.row(row)[start_idx..end_idx].iter().collect();
+                // parse checked that every number fits
+                let num: u32 = num_str.parse().unwrap();
+
+                found(num, row, start_idx, end_idx);
+
+                col = end_idx;
+            } else {
+                col += 1;
+            }
+        }
+    }
+}
+
+pub fn part1(matrix: &Input) -> Answer {
+    let mut sum = 0;
+
+    // Iterate over matrix until we find a digit.
+    // Keep chomping to find the full number, and identify the boundary of coords "around"
+    // the number to look for a symbol. If we find a symbol, then read number into u32 and
+    // add to sum. Then skip to end of number.
+    for_each_number(matrix, |num, row, start_idx, end_idx| {
+        let (startrow, endrow, startcol, endcol) = search_bounds(matrix, row, start_idx, end_idx);
+
+        debug!("Found num {} at [{}, {}], searching for surrounding symbol from [{}..{}, {}..{}]", num, row, start_idx, startrow, endrow, startcol, endcol);
+
+        'outer: for searchrow in startrow .. endrow {
+            trace!("{}", matrix.row(searchrow)[startcol..endcol].iter().collect::<String>());
+            for searchcol in startcol .. endcol {
+                if matrix[(searchrow, searchcol)].is_ascii_punctuation() && matrix[(searchrow, searchcol)] != '.' {
+                    sum += num as Answer;
+                    debug!("Found adjacent symbol {}, adding {} to sum, sum: {}", matrix[(searchrow, searchcol)], num, sum);
+                    break 'outer;
+                }
+            }
+        }
+    });
+
+    sum
+}
+
+pub fn part2(matrix: &Input) -> Answer {
+    // For this part, we'll do the same thing we did in Part 1, but
+    // only search for the '*' gear symbol, and keep track of the
+    // coordinate of this gear along with the adjacent number.
+    // We'll use a HashMap<(usize, usize), Vec<u32>>.
+    let mut gears: HashMap<(usize, usize), Vec<u32>> = HashMap::new();
+
+    for_each_number(matrix, |num, row, start_idx, end_idx| {
+        let (startrow, endrow, startcol, endcol) = search_bounds(matrix, row, start_idx, end_idx);
+
+        debug!("Found num {} at [{}, {}], searching for surrounding * from [{}..{}, {}..{}]", num, row, start_idx, startrow, endrow, startcol, endcol);
+
+        // Each time we find a number adjacent to the gear, go ahead and append that number to
+        // the list of numbers for that gear.
+        'outer: for searchrow in startrow .. endrow {
+            trace!("{}", matrix.row(searchrow)[startcol..endcol].iter().collect::<String>());
+            for searchcol in startcol .. endcol {
+                if matrix[(searchrow, searchcol)] == '*' {
+                    let adjacent_nums = gears.entry((searchrow, searchcol)).or_insert_with(|| {
+                        debug!("Adding gear at coordinate [{}, {}]", searchrow, searchcol);
+                        Vec::new()
+                    });
+
+                    debug!("Adding {} to list of numbers adjacent to gear [{}, {}]", num, searchrow, searchcol);
+                    adjacent_nums.push(num);
+
+                    break 'outer;
+                }
+            }
+        }
+    });
+
+    let mut sum = 0;
+
+    // Now with all gears adjacent to at least one number, iterate through them.
+    // If they are adjacent to exactly 2 numbers, go ahead and multiply them and add to sum.
+    for (gear, adjacent_nums) in &gears {
+        if adjacent_nums.len() == 2 {
+            let product = adjacent_nums[0] as Answer * adjacent_nums[1] as Answer;
+            sum += product;
+            debug!("Gear at [{}, {}] has adjacent nums {}, {}, adding their product {} to sum: {}", gear.0, gear.1, adjacent_nums[0], adjacent_nums[1], product, sum);
+        } else {
+            debug!("Gear found with {} adjacent nums, skipping.", adjacent_nums.len());
+        }
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 4361);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 467835);
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        let e = super::parse("...*.........\n.99999999999.\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "99999999999"));
+        assert!(super::parse("..*........\n4294967295.\n").is_ok());
+
+        // the biggest numbers still add up and multiply
+        let matrix = super::parse("4294967295*4294967295\n.....................\n").unwrap();
+        assert_eq!(super::part1(&matrix), 2 * 4294967295);
+        assert_eq!(super::part2(&matrix), 4294967295 * 4294967295);
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use common::parse::{self, Line};
+use common::ParseError;
+
+pub const DAY: u32 = 4;
+
+// A scratchcard's winning numbers and the numbers you have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub winning: HashSet<u32>,
+    pub yours: HashSet<u32>,
+}
+
+impl Card {
+    // number of your numbers that are also winning numbers
+    pub fn matches(&self) -> usize {
+        self.yours.intersection(&self.winning).count()
+    }
+}
+
+pub type Input = Vec<Card>;
+pub type Answer = u32;
+
+// parse a line like "Card 1: 41 48 83 | 83 86  6" into two sets of integers
+fn parse_card(line: &Line) -> Result<Card, ParseError> {
+    let (card, numbers) = line.split_once(line.text, ":")?;
+    line.strip_prefix(card, "Card")?;
+    let (winning_str, yournums_str) = line.split_once(numbers, "|")?;
+
+    let winning = line.numbers::<u32>(winning_str)?.into_iter().collect::<HashSet<u32>>();
+    let yours = line.numbers::<u32>(yournums_str)?.into_iter().collect::<HashSet<u32>>();
+
+    Ok(Card { winning, yours })
+}
+
+// One card per line, blank lines are ignored.
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    parse::lines(DAY, input).
+        filter(|line| !line.is_blank()).
+        map(|line| parse_card(&line)).
+        collect()
+}
+
+pub fn part1(input: &Input) -> Answer {
+    let mut sum = 0;
+
+    for card in input {
+        let matches = card.matches();
+
+        let mut points = 0;
+        if matches > 0 {
+            points = 2_u32.pow(matches as u32 - 1);
+        }
+
+        sum += points;
+    }
+
+    sum
+}
+
+pub fn part2(input: &Input) -> Answer {
+    // part 2 is kind of a dynamic programming thing. The number of copies for
+    // a given card is the sum of the number of copies for the next M cards,
+    // where M is the number of matches for that given card.
+    // so matches[M] is an vector from part 1.
+    // copies[n] = copies[n+1] + copies[n+2] + ... + copies[n+M]
+    // build up copies backwards from the last card to the first.
+    let matches: Vec<usize> = input.iter().map(|card| card.matches()).collect();
+    let mut copies = vec![0; matches.len()];
+
+    for i in (0..matches.len()).rev() {
+        let mut sum_2 = 1;          // get 1 instance of the original card.
+        for j in 0..matches[i] {
+            sum_2 += copies[i+j+1];
+        }
+        copies[i] = sum_2;
+    }
+
+    // sum all copies
+    copies.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 30);
+    }
+}
@@ -0,0 +1,72 @@
+// Random but valid inputs for property tests.
+use proptest::prelude::*;
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+// One line of a map: the len values from src on map to those from dest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub dest: u64,
+    pub src: u64,
+    pub len: u64,
+}
+
+// An almanac as written in the puzzle, kept small enough that every seed
+// in the part 2 ranges can be checked one by one.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Vec<Range>>,
+}
+
+impl Almanac {
+    // Where a seed ends up, following the puzzle's rules directly.
+    pub fn locate(&self, seed: u64) -> u64 {
+        self.maps.iter().fold(seed, |value, map| {
+            match map.iter().find(|r| value >= r.src && value < r.src + r.len) {
+                Some(r) => r.dest + (value - r.src),
+                None => value,
+            }
+        })
+    }
+
+    // The seed ranges of part 2, as (start, len).
+    pub fn seed_ranges(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.seeds.chunks(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    pub fn to_input(&self) -> String {
+        let seeds: Vec<String> = self.seeds.iter().map(|s| s.to_string()).collect();
+        let mut text = format!("seeds: {}\n", seeds.join(" "));
+
+        for (i, map) in self.maps.iter().enumerate() {
+            text += &format!("\n{}-to-{} map:\n", CATEGORIES[i], CATEGORIES[i + 1]);
+            for r in map {
+                text += &format!("{} {} {}\n", r.dest, r.src, r.len);
+            }
+        }
+
+        text
+    }
+}
+
+// Source ranges that don't overlap (but may touch), listed in any order.
+pub fn map() -> impl Strategy<Value = Vec<Range>> {
+    prop::collection::vec((0u64..8, 1u64..15, 0u64..120), 0..6).prop_flat_map(|specs| {
+        let mut start = 0;
+        let mut ranges = Vec::new();
+        for (gap, len, dest) in specs {
+            ranges.push(Range { dest, src: start + gap, len });
+            start += gap + len;
+        }
+        Just(ranges).prop_shuffle()
+    })
+}
+
+pub fn almanac() -> impl Strategy<Value = Almanac> {
+    let seeds = prop::collection::vec((0u64..100, 1u64..20), 1..5).
+        prop_map(|pairs| pairs.into_iter().flat_map(|(start, len)| [start, len]).collect());
+    let maps = prop::collection::vec(map(), CATEGORIES.len() - 1);
+
+    (seeds, maps).prop_map(|(seeds, maps)| Almanac { seeds, maps })
+}
@@ -0,0 +1,318 @@
+use common::{parse, ParseError};
+
+pub const DAY: u32 = 5;
+
+#[cfg(test)]
+mod gen;
+
+// src start, src end, dest start, dest end (the ends are inclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping(pub u64, pub u64, pub u64, pub u64);
+
+// Map source value to destination value based on mappings.
+pub fn s2d(i : u64, ms : &[Mapping]) -> u64 {
+    // first, find mapping where i is between
+    // the source start and end
+    for m in ms {
+        if i >= m.0 && i <= m.1 {
+            // now, find the destination value
+            // by subtracting the source start
+            // from i, and adding that to the
+            // destination start
+            return m.2 + (i - m.0);
+        }
+    }
+
+    // if we get here, we didn't find a mapping
+    // so return the same number, since there's always an assumed 1-to-1 mapping.
+    i
+}
+
+// Store the beginning and end of values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRange(pub u64, pub u64);
+
+// Split a range of values into smaller ranges based on mappings.
+pub fn split_range (i : MapRange, ms : &[Mapping]) -> Vec<MapRange> {
+    let mut result : Vec<MapRange> = Vec::new();
+
+    for m in ms {
+        if i.0 >= m.0 && i.1 <= m.1 {
+            // range contained entirely in a mapping, no need to split
+            result.push(MapRange(i.0, i.1));
+            break;
+        } else if i.1 < m.0 || i.0 > m.1 {
+            // range does not overlap in this mapping, check next one.
+            continue;
+        } else if i.0 < m.0 && i.1 <= m.1 {
+            // range overlaps with beginning of mapping, split into two
+            // ranges, one before the mapping, and one inside the mapping.
+            // recursively check the left range for more mappings.
+            let mut left_range = split_range(MapRange(i.0, m.0 - 1), ms);
+            result.append(&mut left_range);     // before mapping
+            result.push(MapRange(m.0, i.1));    // inside mapping
+        } else if i.0 >= m.0 && i.1 > m.1 {
+            // range overlaps with end of mapping, split into two 
+            // ranges, one inside the mapping, and one after the mapping.
+            // recursively check the right range for more mappings.
+            let mut right_range = split_range(MapRange(m.1 + 1, i.1), ms);
+            result.append(&mut right_range);    // after mapping
+            result.push(MapRange(i.0, m.1));    // inside mapping
+        } else if i.0 < m.0 && i.1 > m.1 {
+            // range overlaps with entire mapping, split into three
+            // ranges, one before the mapping, one inside the mapping,
+            // and one after the mapping.
+            // recursively check the left and right ranges for more mappings.
+            let mut left_range = split_range(MapRange(i.0, m.0 - 1), ms);
+            result.append(&mut left_range);     // before mapping
+            result.push(MapRange(m.0, m.1));    // inside mapping
+            let mut right_range = split_range(MapRange(m.1 + 1, i.1), ms);
+            result.append(&mut right_range);    // after mapping
+        }
+    }
+
+    // if no mappings intersect with this range, return the same range.
+    if result.is_empty() {
+        result.push(MapRange(i.0, i.1));
+    }
+
+    result
+}
+
+// map source range to dest range based on mappings. Input range is assumed to be
+// split such that overlaps with either 1 or 0 mappings.
+pub fn s2ds(i : MapRange, ms : &[Mapping]) -> MapRange {
+    for m in ms {
+        if i.0 >= m.0 && i.1 <= m.1 {
+            // shift input range into destination range by how far it is
+            // from the src start, which keeps it inside the dest range.
+            return MapRange(m.2 + (i.0 - m.0), m.2 + (i.1 - m.0));
+        }
+    }
+
+    // if we get here, we didn't find a mapping so return the same range, 
+    // since there's always an assumed 1-to-1 mapping.
+    i
+}
+
+// The seeds to be planted, and the seed-to-soil, soil-to-fertilizer, ...,
+// humidity-to-location maps, in that order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Vec<Mapping>>,
+}
+
+pub type Input = Almanac;
+pub type Answer = u64;
+
+// The last value of a range of len values from start, if that fits in a u64.
+fn range_end(start: u64, len: u64) -> Option<u64> {
+    start.checked_add(len - 1)
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut lines = parse::lines(DAY, input).filter(|line| !line.is_blank());
+
+    // first line is list of seeds
+    let Some(line) = lines.next() else {
+        return Err(ParseError::at_end(DAY, input, "expected a list of seeds"));
+    };
+    let seed_list = line.strip_prefix(line.text, "seeds:")?;
+    let seeds : Vec<u64> = line.numbers(seed_list)?;
+
+    // part 2 reads the seeds as pairs of a start and a length
+    if !seeds.len().is_multiple_of(2) {
+        return Err(line.error(line.text, format!("expected pairs of seed numbers, found {} numbers", seeds.len())));
+    }
+    let tokens : Vec<&str> = seed_list.split_whitespace().collect();
+    for (i, pair) in seeds.chunks(2).enumerate() {
+        if pair[1] == 0 {
+            return Err(line.error(tokens[i * 2 + 1], "expected a seed range of at least 1 seed"));
+        }
+        if range_end(pair[0], pair[1]).is_none() {
+            return Err(line.error(tokens[i * 2 + 1], "expected the seed range to end within a u64"));
+        }
+    }
+
+    let mut maps : Vec<Vec<Mapping>> = Vec::new();
+
+    // iterate through rest of lines. Each "category" header starts a new
+    // map, and we'll add mappings to the most recent one.
+    for line in lines {
+        if line.text.ends_with("map:") {
+            maps.push(Vec::new());
+        } else {
+            // parse this line into a mapping.
+            let Some(current_map) = maps.last_mut() else {
+                return Err(line.error(line.text, "expected a map header like 'seed-to-soil map:'"));
+            };
+            let parts : Vec<u64> = line.numbers(line.text)?;
+            let [dest, src, len] = parts[..] else {
+                return Err(line.error(line.text, format!("expected 3 numbers, found {}", parts.len())));
+            };
+            // a range of no values maps nothing, and its inclusive end
+            // would be before its start
+            if len > 0 {
+                let tokens : Vec<&str> = line.text.split_whitespace().collect();
+                let (Some(src_end), Some(dest_end)) = (range_end(src, len), range_end(dest, len)) else {
+                    return Err(line.error(tokens[2], "expected the mapping to end within a u64"));
+                };
+                current_map.push(Mapping(src, src_end, dest, dest_end));
+            }
+        }
+    }
+
+    Ok(Almanac { seeds, maps })
+}
+
+pub fn part1(almanac: &Input) -> Answer {
+    let mut min_locn = u64::MAX;
+
+    // run each seed through every map in turn to find its location
+    for seed in &almanac.seeds {
+        let locn = almanac.maps.iter().fold(*seed, |i, ms| s2d(i, ms));
+
+        if locn < min_locn {
+            min_locn = locn;
+        }
+    }
+
+    min_locn
+}
+
+pub fn part2(almanac: &Input) -> Answer {
+    // For part 2 we need to consider ranges of seeds.
+    // parse seeds into ranges
+    let mut ranges : Vec<MapRange> = Vec::new();
+    for pair in almanac.seeds.chunks(2) {
+        // second number is the _number of values_, so need to take one less.
+        // parse checked this fits.
+        ranges.push(MapRange(pair[0], pair[0] + (pair[1] - 1)));
+    }
+
+    // split each range so it overlaps with either 1 or 0 mappings, then
+    // shift it into the next category, one map at a time.
+    for ms in &almanac.maps {
+        let mut next_ranges : Vec<MapRange> = Vec::new();
+
+        for range in ranges {
+            for split in split_range(range, ms) {
+                next_ranges.push(s2ds(split, ms));
+            }
+        }
+
+        ranges = next_ranges;
+    }
+
+    // now find smallest location in the location ranges.
+    let mut most_min_locn = u64::MAX;   // smallest location seen so far
+    for lr in &ranges {
+        if lr.0 < most_min_locn {
+            most_min_locn = lr.0;
+        }
+    }
+
+    most_min_locn
+}
+
+#[cfg(test)]
+mod tests {
+    use common::differential;
+    use common::input::Source;
+    use proptest::prelude::*;
+
+    use crate::gen;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 35);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 46);
+    }
+
+    // a mapping's last value is len - 1 past its start, and one with len 0
+    // maps nothing
+    #[test]
+    fn test_mapping_ends() {
+        let almanac = super::parse("seeds: 15 15\n\nseed-to-soil map:\n50 10 5\n").unwrap();
+        assert_eq!(almanac.maps[0], [super::Mapping(10, 14, 50, 54)]);
+        assert_eq!(super::part1(&almanac), 15);
+        assert_eq!(super::part2(&almanac), 15);
+
+        let almanac = super::parse("seeds: 5 5\n\nseed-to-soil map:\n0 5 0\n").unwrap();
+        assert!(almanac.maps[0].is_empty());
+        assert_eq!(super::part1(&almanac), 5);
+    }
+
+    #[test]
+    fn test_parse_seeds() {
+        let e = super::parse("seeds: 79 14 55\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.message, "expected pairs of seed numbers, found 3 numbers");
+
+        let e = super::parse("seeds: 79 14 55 0\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 17, "0"));
+
+        // the last of u64's values is fine, but not one more
+        let max = u64::MAX;
+        assert!(super::parse(&format!("seeds: {} 1\n", max)).is_ok());
+        let e = super::parse(&format!("seeds: {} 2\n", max)).unwrap_err();
+        assert_eq!((e.text.as_str(), e.message.as_str()), ("2", "expected the seed range to end within a u64"));
+        let e = super::parse(&format!("seeds: 1 1\n\nseed-to-soil map:\n{} 0 2\n", max)).unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (4, "2"));
+        let e = super::parse(&format!("seeds: 1 1\n\nseed-to-soil map:\n0 {} 2\n", max)).unwrap_err();
+        assert_eq!(e.message, "expected the mapping to end within a u64");
+
+        // mapping to the top of the range, and back down from it
+        let almanac = super::parse(&format!("seeds: 5 4\n\nseed-to-soil map:\n{} 4 5\n\nsoil-to-fertilizer map:\n0 {} 2\n", max - 4, max - 1)).unwrap();
+        assert_eq!(super::part1(&almanac), max - 4);
+        assert_eq!(super::part2(&almanac), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1_locations(almanac in gen::almanac()) {
+            let parsed = super::parse(&almanac.to_input()).unwrap();
+            let expected = almanac.seeds.iter().map(|&seed| almanac.locate(seed)).min().unwrap();
+            prop_assert_eq!(super::part1(&parsed), expected);
+        }
+
+        // part 2 can't do worse than the start of any of its ranges, and if
+        // every range holds a single seed that's all there is
+        #[test]
+        fn test_part2_bounds(mut almanac in gen::almanac()) {
+            let parsed = super::parse(&almanac.to_input()).unwrap();
+            let best_start = almanac.seed_ranges().map(|(start, _)| almanac.locate(start)).min().unwrap();
+            prop_assert!(super::part2(&parsed) <= best_start);
+
+            for pair in almanac.seeds.chunks_mut(2) {
+                pair[1] = 1;
+            }
+            let parsed = super::parse(&almanac.to_input()).unwrap();
+            prop_assert_eq!(super::part2(&parsed), best_start);
+        }
+    }
+
+    // part 2 without any range splitting: map every seed on its own
+    fn part2_brute(almanac: &super::Almanac) -> u64 {
+        almanac.seeds.chunks(2).
+            flat_map(|pair| pair[0]..pair[0] + pair[1]).
+            map(|seed| almanac.maps.iter().fold(seed, |i, ms| super::s2d(i, ms))).
+            min().unwrap()
+    }
+
+    differential!(test_part2_brute, gen::almanac().prop_map(|almanac| super::parse(&almanac.to_input()).unwrap()),
+                  part2_brute, super::part2);
+}
@@ -0,0 +1,12 @@
+// Random but valid inputs for property tests.
+use proptest::prelude::*;
+
+// A race as (time, record distance). Some records are at or past the best
+// distance the race allows, so can't be beaten, and races of 0 or 1 can't be
+// won at all.
+pub fn race() -> impl Strategy<Value = (u64, u64)> {
+    prop_oneof![0u64..4, 0u64..5000].prop_flat_map(|time| {
+        let best = (time / 2) * (time - time / 2);
+        (Just(time), 0..=best + best / 8 + 2)
+    })
+}
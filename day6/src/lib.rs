@@ -0,0 +1,229 @@
+use common::parse::{self, Line};
+use common::ParseError;
+
+pub const DAY: u32 = 6;
+
+#[cfg(test)]
+mod gen;
+
+// The race times and record distances, one entry per race.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Races {
+    pub times: Vec<u64>,
+    pub dists: Vec<u64>,
+}
+
+pub type Input = Races;
+pub type Answer = u64;
+
+// parse a line like "Time:      7  15   30", with the given label
+fn parse_line(line: &Line, label: &str) -> Result<Vec<u64>, ParseError> {
+    line.numbers(line.strip_prefix(line.text, label)?)
+}
+
+// For Part 2, treat the numbers on a line as a concatenation of digits.
+// None if there are no numbers or they make one too big for a u64.
+fn concat(nums: &[u64]) -> Option<u64> {
+    let ret_str: String = nums.iter().map(|x| x.to_string()).collect();
+
+    ret_str.parse::<u64>().ok()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut lines = parse::lines(DAY, input);
+
+    let (Some(time_line), Some(dist_line)) = (lines.next(), lines.next()) else {
+        return Err(ParseError::at_end(DAY, input, "expected a Time: line and a Distance: line"));
+    };
+    let times = parse_line(&time_line, "Time:")?;
+    let dists = parse_line(&dist_line, "Distance:")?;
+
+    if times.is_empty() {
+        return Err(time_line.error(time_line.text, "expected at least one race"));
+    }
+    if times.len() != dists.len() {
+        return Err(dist_line.error(dist_line.text, format!("expected {} distances to match the times, found {}", times.len(), dists.len())));
+    }
+
+    // part 2 runs all of the digits on a line together
+    for (line, nums) in [(&time_line, &times), (&dist_line, &dists)] {
+        if concat(nums).is_none() {
+            return Err(line.error(line.text, "expected the digits together to fit in a u64"));
+        }
+    }
+
+    Ok(Races { times, dists })
+}
+
+// given the distance we're trying to beat, the time given, and
+// the time we hold down the button, will this beat the record? A distance
+// too far for a u64 beats any record.
+pub fn is_winner(time: u64, dist: u64, hold_time: u64) -> bool {
+    let speed = hold_time;
+    let run_time = time.saturating_sub(hold_time);
+
+    run_time.checked_mul(speed).is_none_or(|run_dist| run_dist > dist)
+}
+
+// Count the winning hold times by trying every one of them.
+pub fn ways_to_win_brute(time: u64, dist: u64) -> u64 {
+    let mut ways_to_win = 0;
+
+    for hold_time in 0..time {
+        if is_winner(time, dist, hold_time) {
+            ways_to_win += 1;
+        }
+    }
+
+    ways_to_win
+}
+
+// Count the winning hold times by searching for the shortest and longest.
+pub fn ways_to_win(time: u64, dist: u64) -> u64 {
+    // We expect a min winning hold time and a max hold time, and everything in
+    // between is a winner. We'll do a binary search of hold times, looking
+    // for hold time that produces a loser next to a hold time that produces
+    // a winner. The value of the winner is the min hold time. We'll do the
+    // same but looking for a winner next to a loser, and the value of the
+    // winner there is the max hold time. Max hold time - min hold time is
+    // the number of winners.
+    //
+    // LLLLLLLLLLWWWWWWWWWWWWWLLLLLLLLLLLLL
+    //          ^^           ^^
+    //          min          max
+
+    // holding for half the time goes furthest, so if that doesn't win
+    // nothing does, and there are no bounds to search for
+    if !is_winner(time, dist, time / 2) {
+        return 0;
+    }
+
+    let mut win_low : u64 = 0;      // min successful hold time
+    let mut win_high : u64 = 0;     // max successful hold time
+
+    // search for min successful hold time in lower half to start.
+    let mut low : u64 = 0;
+    let mut high : u64 = time / 2;
+
+    loop {
+        let try_hold = low + (high - low) / 2;
+
+        let left_win = is_winner(time, dist, try_hold);
+        let right_win = is_winner(time, dist, try_hold + 1);
+
+        if left_win && right_win {
+            // two winners next to each other, ignore everything to our right
+            high = try_hold - 1;
+        } else if !left_win && !right_win {
+            // two losers next to each other, ignore everything to our left
+            low = try_hold + 1;
+        } else if !left_win && right_win {
+            // we found a loser, winner, we found lower bounds.
+            win_low = try_hold;
+            break;
+        } else if left_win && !right_win {
+            // we found a winner, loser, we found upper bounds.
+            win_high = try_hold;
+            break;
+        }
+    }
+
+    // search again, but this time we expect winners to our left, losers to our right
+    low = time / 2;
+    high = time;
+
+    loop {
+        let try_hold = low + (high - low) / 2;
+
+        let left_win = is_winner(time, dist, try_hold);
+        let right_win = is_winner(time, dist, try_hold + 1);
+
+        if left_win && right_win {
+            low = try_hold + 1;
+        } else if !left_win && !right_win {
+            high = try_hold - 1;
+        } else if !left_win && right_win {
+            win_low = try_hold;
+            break;
+        } else if left_win && !right_win {
+            win_high = try_hold;
+            break;
+        }
+    }
+
+    win_high - win_low
+}
+
+pub fn part1(races: &Input) -> Answer {
+    let mut possible_wins = 1;
+
+    // brute-force it.
+    for race in 0..races.times.len() {
+        possible_wins *= ways_to_win_brute(races.times[race], races.dists[race]);
+    }
+
+    possible_wins
+}
+
+pub fn part2(races: &Input) -> Answer {
+    // parse checked that both of these fit
+    ways_to_win(concat(&races.times).unwrap(), concat(&races.dists).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::differential;
+    use common::input::Source;
+
+    use crate::gen;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 288);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 71503);
+    }
+
+    #[test]
+    fn test_ways_to_win_edges() {
+        // records that can't be beaten, and races too short to go anywhere
+        assert_eq!(super::ways_to_win(5, 100), 0);
+        assert_eq!(super::ways_to_win(5, 6), 0);
+        assert_eq!(super::ways_to_win(0, 0), 0);
+        assert_eq!(super::ways_to_win(1, 0), 0);
+        let races = super::parse("Time: 5\nDistance: 100\n").unwrap();
+        assert_eq!((super::part1(&races), super::part2(&races)), (0, 0));
+
+        // going further than a u64 reaches still wins
+        assert!(super::is_winner(u64::MAX, u64::MAX - 1, u64::MAX / 2));
+        assert_eq!(super::ways_to_win(10_000_000_000, 0), 9_999_999_999);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = super::parse("Time:\nDistance:\n").unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (1, "expected at least one race"));
+
+        // 20 digits run together is more than a u64 holds
+        let e = super::parse("Time: 9999999999 9999999999\nDistance: 1 2\n").unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (1, "expected the digits together to fit in a u64"));
+        let e = super::parse("Time: 1 2\nDistance: 9999999999 9999999999\n").unwrap_err();
+        assert_eq!(e.line, 2);
+    }
+
+    // the binary search against trying every hold time
+    differential!(test_ways_to_win, gen::race(),
+                  |&(time, dist): &(u64, u64)| super::ways_to_win_brute(time, dist),
+                  |&(time, dist): &(u64, u64)| super::ways_to_win(time, dist));
+}
@@ -0,0 +1,32 @@
+// Random but valid inputs for property tests.
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::{Play, CARDS};
+
+fn hand_from(cards: &'static str) -> impl Strategy<Value = String> {
+    let cards: Vec<char> = cards.chars().collect();
+    prop::collection::vec(select(cards), 5).prop_map(|hand| hand.into_iter().collect())
+}
+
+pub fn hand() -> impl Strategy<Value = String> {
+    hand_from(CARDS)
+}
+
+pub fn hand_without_jokers() -> impl Strategy<Value = String> {
+    hand_from("AKQT98765432")
+}
+
+// Distinct hands, as in the puzzle, each with a bid.
+pub fn plays(hand: impl Strategy<Value = String>) -> impl Strategy<Value = Vec<Play>> {
+    prop::collection::hash_set(hand, 1..60).prop_flat_map(|hands| {
+        let count = hands.len();
+        (Just(hands), prop::collection::vec(1u32..=1000, count)).prop_map(|(hands, bids)| {
+            hands.into_iter().zip(bids).map(|(hand_str, bid)| Play { hand_str, bid }).collect()
+        })
+    })
+}
+
+pub fn to_input(plays: &[Play]) -> String {
+    plays.iter().map(|play| format!("{} {}\n", play.hand_str, play.bid)).collect()
+}
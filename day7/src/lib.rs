@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use common::parse::{self, Line};
+use common::ParseError;
+use log::debug;
+
+pub const DAY: u32 = 7;
+
+#[cfg(test)]
+mod gen;
+
+#[derive(Debug, Clone, Copy, Ord, PartialEq, PartialOrd, Eq)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+// Given the card character, return the value of the card.
+pub fn card_val(c : char) -> u8 {
+    match c {
+        'A' => 14,
+        'K' => 13,
+        'Q' => 12,
+        'J' => 11,
+        'T' => 10,
+         _   => c.to_digit(10).unwrap() as u8,
+    }
+}
+
+// Given the card character (with Jokers wild), return the value of the card.
+pub fn card_val2(c : char) -> u8 {
+    match c {
+        'A' => 14,
+        'K' => 13,
+        'Q' => 12,
+        'J' => 1,
+        'T' => 10,
+         _   => c.to_digit(10).unwrap() as u8,
+    }
+}
+
+#[derive(Debug)]
+struct Hand {
+    hand_str: String,
+    hand_type: HandType,
+    bid: u32,
+}
+
+fn cmp(a : &Hand, b: &Hand) -> std::cmp::Ordering {
+    if a.hand_type > b.hand_type {
+        std::cmp::Ordering::Greater
+    } else if a.hand_type < b.hand_type {
+        std::cmp::Ordering::Less
+    } else {
+        // same type of hand, need to compare individual cards now.
+        // iterate until we find one worth more than its counterpart
+        for i in 0..a.hand_str.len() {
+            let self_val = card_val(a.hand_str.chars().nth(i).unwrap());
+            let other_val = card_val(b.hand_str.chars().nth(i).unwrap());
+            if self_val < other_val {
+                return std::cmp::Ordering::Less;
+            } else if self_val > other_val {
+                return std::cmp::Ordering::Greater;
+            }
+        }
+
+        // if we get here, the hands are identical.
+        std::cmp::Ordering::Equal
+    }
+}
+
+// Compare hands with Jokers wild
+fn cmp2(a : &Hand, b: &Hand) -> std::cmp::Ordering {
+    if a.hand_type > b.hand_type {
+        std::cmp::Ordering::Greater
+    } else if a.hand_type < b.hand_type {
+        std::cmp::Ordering::Less
+    } else {
+        // same type of hand, need to compare individual cards now.
+        // iterate until we find one worth more than its counterpart
+        for i in 0..a.hand_str.len() {
+            let self_val = card_val2(a.hand_str.chars().nth(i).unwrap());
+            let other_val = card_val2(b.hand_str.chars().nth(i).unwrap());
+
+            if self_val < other_val {
+                return std::cmp::Ordering::Less;
+            } else if self_val > other_val {
+                return std::cmp::Ordering::Greater;
+            }
+        }
+
+        // if we get here, the hands are identical.
+        std::cmp::Ordering::Equal
+    }
+}
+
+// given a string with a particular hand, determine the hand type.
+pub fn hand_type(hand_str : &str) -> HandType {
+    let mut card_counts : HashMap<char, u8> = HashMap::new();
+
+    for c in ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'] {
+        card_counts.insert(c, 0);
+    }
+    // populate card counts
+    for c in hand_str.chars() {
+        card_counts.insert(c, card_counts.get(&c).unwrap() + 1);
+    }
+
+    // determine hand type
+    for c in ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'] {
+        if *card_counts.get(&c).unwrap() == 5 {
+            return HandType::FiveOfAKind;
+        } else if *card_counts.get(&c).unwrap() == 4 {
+            return HandType::FourOfAKind;
+        } else if *card_counts.get(&c).unwrap() == 3 {
+            // check for full house
+            for c2 in ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'] {
+                if *card_counts.get(&c2).unwrap() == 2 {
+                    return HandType::FullHouse;
+                }
+            }
+            return HandType::ThreeOfAKind;
+        } else if *card_counts.get(&c).unwrap() == 2 {
+            // check for full house
+            for c2 in ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'] {
+                if *card_counts.get(&c2).unwrap() == 3 {
+                    return HandType::FullHouse;
+                }
+            }
+            // check for two pair
+            for c2 in ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'] {
+                if c2 != c && *card_counts.get(&c2).unwrap() == 2 {
+                    return HandType::TwoPair;
+                }
+            }
+            return HandType::OnePair;
+        }
+    }
+
+    HandType::HighCard
+}
+
+// Jokers wild version
+pub fn hand_type2(hand_str : &str) -> HandType {
+    // determine hand type with jokers _removed_
+    let hand_str2_binding = hand_str.replace("J","");
+    let hand_str2 = hand_str2_binding.as_str();
+    let hand_type_no_joker = hand_type(hand_str2);
+    let joker_count = hand_str.len() - hand_str2.len();
+
+    if joker_count >= 4 {
+        // jokers can match whatever is left over
+        HandType::FiveOfAKind
+    } else if joker_count == 3 {
+        if hand_type_no_joker == HandType::OnePair {
+            HandType::FiveOfAKind
+        } else {
+            HandType::FourOfAKind
+        }
+    } else if joker_count == 2 {
+        if hand_type_no_joker == HandType::ThreeOfAKind {
+            // deck had 3 cards that matched
+            HandType::FiveOfAKind
+        } else if hand_type_no_joker == HandType::OnePair {
+            // deck had 2 matching cards
+            HandType::FourOfAKind
+        } else {
+            // deck had 3 other non-matching cards
+            HandType::ThreeOfAKind
+        }
+    } else if joker_count == 1 {
+        if hand_type_no_joker == HandType::FourOfAKind {
+            // deck had 4 cards that matched
+            HandType::FiveOfAKind
+        } else if hand_type_no_joker == HandType::ThreeOfAKind {
+            // deck had 3 cards that matched
+            HandType::FourOfAKind
+        } else if hand_type_no_joker == HandType::TwoPair {
+            HandType::FullHouse
+        } else if hand_type_no_joker == HandType::OnePair {
+            // deck had 2 matching cards
+            HandType::ThreeOfAKind
+        } else {
+            // deck had 4 other non-matching cards,
+            // we can make a pair.
+            HandType::OnePair
+        }
+    } else {
+        // no jokers, hand is what it was
+        hand_type_no_joker
+    }
+}
+
+// A hand of cards and the bid placed on it, as listed in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Play {
+    pub hand_str: String,
+    pub bid: u32,
+}
+
+pub type Input = Vec<Play>;
+pub type Answer = u32;
+
+const CARDS : &str = "AKQJT98765432";
+
+// parse a line like "32T3K 765" into a hand and a bid
+fn parse_play(line: &Line) -> Result<Play, ParseError> {
+    let (hand_str, bid_str) = line.split_once(line.text.trim(), " ")?;
+
+    if hand_str.chars().count() != 5 {
+        return Err(line.error(hand_str, "expected a hand of 5 cards"));
+    }
+    if let Some((i, c)) = hand_str.char_indices().find(|(_, c)| !CARDS.contains(*c)) {
+        return Err(line.error(&hand_str[i..i + c.len_utf8()], format!("expected one of the cards {}", CARDS)));
+    }
+
+    Ok(Play {
+        hand_str: hand_str.to_string(),
+        bid: line.number(bid_str.trim())?,
+    })
+}
+
+// One hand per line, blank lines are ignored.
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    parse::lines(DAY, input).
+        filter(|line| !line.is_blank()).
+        map(|line| parse_play(&line)).
+        collect()
+}
+
+// Given hands sorted from weakest to strongest, add up each bid multiplied
+// by the rank of its hand.
+fn total_winnings(hands : &[Hand]) -> u32 {
+    let mut total_score = 0;
+
+    for (i, hand) in hands.iter().enumerate() {
+        let rank = i+1;
+        let score = hand.bid * rank as u32;
+        total_score += score;
+        debug!("Hand: {}, type: {:?}, rank: {}, bid: {}, score: {}", hand.hand_str, hand.hand_type, rank, hand.bid, score);
+    }
+
+    total_score
+}
+
+pub fn part1(input: &Input) -> Answer {
+    let mut hands : Vec<Hand> = input.iter().map(|play| Hand {
+        hand_str: play.hand_str.clone(),
+        hand_type: hand_type(&play.hand_str),
+        bid: play.bid,
+    }).collect();
+
+    hands.sort_by(cmp);
+
+    total_winnings(&hands)
+}
+
+pub fn part2(input: &Input) -> Answer {
+    let mut hands2 : Vec<Hand> = input.iter().map(|play| Hand {
+        hand_str: play.hand_str.clone(),
+        hand_type: hand_type2(&play.hand_str),
+        bid: play.bid,
+    }).collect();
+
+    // re-sort with Jokers wild rules
+    hands2.sort_by(cmp2);
+
+    total_winnings(&hands2)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+    use proptest::prelude::*;
+
+    use crate::gen;
+
+    // parse one of the example inputs in this day's directory
+    fn example(filename: &str) -> super::Input {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), filename).read().
+            expect("Something went wrong reading the file");
+
+        super::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(&example("input_test.txt")), 6440);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(&example("input_test.txt")), 5905);
+    }
+
+    #[test]
+    fn test_card_val() {
+        assert_eq!(super::card_val('A'), 14);
+        assert_eq!(super::card_val('K'), 13);
+        assert_eq!(super::card_val('2'), 2);
+    }
+    
+    #[test]
+    fn test_hand_type() {
+        assert_eq!(super::hand_type("AAAAA"), super::HandType::FiveOfAKind);
+        assert_eq!(super::hand_type("22255"), super::HandType::FullHouse);
+        assert_eq!(super::hand_type("22AA2"), super::HandType::FullHouse);
+    }
+
+    #[test]
+    fn test_hand_type2() {
+        assert_eq!(super::hand_type2("AAAAA"), super::HandType::FiveOfAKind);
+        assert_eq!(super::hand_type2("22255"), super::HandType::FullHouse);
+        assert_eq!(super::hand_type2("22J55"), super::HandType::FullHouse);
+        assert_eq!(super::hand_type2("22AJJ"), super::HandType::FourOfAKind);
+        assert_eq!(super::hand_type2("32T3K"), super::HandType::OnePair);
+        assert_eq!(super::hand_type2("T55J5"), super::HandType::FourOfAKind);
+    }
+
+    proptest! {
+        // jokers act like whichever card makes the hand strongest
+        #[test]
+        fn test_jokers_wild(hand in gen::hand()) {
+            let best = super::CARDS.chars().
+                map(|c| super::hand_type(&hand.replace('J', &c.to_string()))).
+                max().unwrap();
+            prop_assert_eq!(super::hand_type2(&hand), best);
+        }
+
+        // with every bid 1 the winnings are 1 + 2 + ... + n, whatever the hands
+        #[test]
+        fn test_unit_bids(plays in gen::plays(gen::hand())) {
+            let plays: Vec<super::Play> = plays.into_iter().map(|play| super::Play { bid: 1, ..play }).collect();
+            let n = plays.len() as u32;
+            let parsed = super::parse(&gen::to_input(&plays)).unwrap();
+            prop_assert_eq!(super::part1(&parsed), n * (n + 1) / 2);
+            prop_assert_eq!(super::part2(&parsed), n * (n + 1) / 2);
+        }
+
+        // the order of the lines doesn't matter
+        #[test]
+        fn test_order((plays, shuffled) in gen::plays(gen::hand()).prop_flat_map(|plays| (Just(plays.clone()), Just(plays).prop_shuffle()))) {
+            let parsed = super::parse(&gen::to_input(&plays)).unwrap();
+            let reparsed = super::parse(&gen::to_input(&shuffled)).unwrap();
+            prop_assert_eq!(super::part1(&parsed), super::part1(&reparsed));
+            prop_assert_eq!(super::part2(&parsed), super::part2(&reparsed));
+        }
+
+        // without any jokers both parts rank the hands the same way
+        #[test]
+        fn test_no_jokers(plays in gen::plays(gen::hand_without_jokers())) {
+            let parsed = super::parse(&gen::to_input(&plays)).unwrap();
+            prop_assert_eq!(super::part1(&parsed), super::part2(&parsed));
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use num::integer::lcm;
+
+use common::parse::{self, Line};
+use common::{config, frames, ParseError};
+use log::{info, warn};
+
+pub const DAY: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub left  : String,
+    pub right : String,
+}
+
+// The left/right instructions and the map of labelled nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    pub path  : String,
+    pub nodes : HashMap<String, Node>,
+}
+
+pub type Input = Network;
+pub type Answer = usize;
+
+// parse a line like "AAA = (BBB, CCC)" into its label and node
+fn parse_node<'a>(line : &Line<'a>) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+    let (label, rest) = line.split_once(line.text, " = ")?;
+    let rest = line.strip_prefix(rest, "(")?;
+    let (left, rest) = line.split_once(rest, ", ")?;
+    let (right, rest) = line.split_once(rest, ")")?;
+
+    if !rest.trim().is_empty() {
+        return Err(line.error(rest, "unexpected text after node"));
+    }
+
+    Ok((label, left, right))
+}
+
+pub fn parse(s : &str) -> Result<Input, ParseError> {
+    let mut lines = parse::lines(DAY, s).filter(|line| !line.is_blank());
+    let mut nodes: HashMap<String, Node> = HashMap::new();
+
+    // first line is the path of left/right instructions
+    let Some(path_line) = lines.next() else {
+        return Err(ParseError::at_end(DAY, s, "expected a path of L and R instructions"));
+    };
+    let path = path_line.text.trim();
+    if let Some((i, c)) = path.char_indices().find(|(_, c)| *c != 'L' && *c != 'R') {
+        return Err(path_line.error(&path[i..i + c.len_utf8()], "expected L or R"));
+    }
+
+    // parse nodes, remembering where each neighbour was mentioned so we
+    // can check they all exist once we've seen every node.
+    let mut mentions: Vec<(Line, &str)> = Vec::new();
+
+    for line in lines {
+        let (label, left, right) = parse_node(&line)?;
+
+        nodes.insert(label.to_string(), Node{left: left.to_string(),
+                                                  right: right.to_string()});
+        mentions.push((line, left));
+        mentions.push((line, right));
+    }
+
+    if let Some((line, label)) = mentions.iter().find(|(_, label)| !nodes.contains_key(*label)) {
+        return Err(line.error(label, format!("no node labelled {}", label)));
+    }
+
+    Ok(Network { path: path.to_string(), nodes })
+}
+
+// Take a single step from cur, following the instruction at path_idx.
+fn step (network : &Network, cur : &str, path_idx : usize) -> String {
+    let c : char = network.path.as_bytes()[path_idx] as char;
+    match c {
+        'L' => {
+            network.nodes.get(cur).unwrap().left.to_string()
+        },
+        'R' => {
+            network.nodes.get(cur).unwrap().right.to_string()
+        },
+        _ => {
+            warn!("Invalid character in path: {}", c);
+            cur.to_string()
+        }
+    }
+}
+
+// There are only so many places to be, each node at each point in the
+// path, so a walk that's taken more steps than that without finishing has
+// gone round in a circle and never will.
+fn max_steps (network : &Network) -> usize {
+    network.nodes.len() * network.path.len()
+}
+
+pub fn steps_to_zzz (starting_node : &str, end : &str, network : &Network) -> Result<usize, String> {
+    // Grr - instructions say "start with AAA", NOT the first line. :facepalm:
+    let mut cur : String = starting_node.to_string();
+    let mut steps = 0;
+    let mut path_idx = 0;
+
+    while cur != end {
+        if steps > max_steps(network) {
+            return Err(format!("{} never reaches {}", starting_node, end));
+        }
+        if path_idx == network.path.len() {
+            path_idx = 0;
+        }
+
+        cur = step(network, &cur, path_idx);
+        frames::status(|| format!("from {}, step {}: {} to {}", starting_node, steps + 1,
+                                  network.path.as_bytes()[path_idx] as char, cur));
+
+        path_idx += 1;
+        steps += 1;
+    }
+
+    Ok(steps)
+}
+
+// Part 2. Find paths to xxZ nodes. We'll keep going until we hit the same
+// node at the same place in the path, then we'll know we've found a cycle of Z
+// nodes. We'll return a vector of the visited Z nodes with their respective
+// number of steps. The least common multiple of these will be the answer.
+pub fn steps_to_xxz (starting_node : &str, end_suffix : &str, network : &Network) -> Result<usize, String> {
+    let mut cur : String = starting_node.to_string();
+    let mut steps = 1;
+    let mut path_idx = 0;
+
+    loop {
+        if steps > max_steps(network) {
+            return Err(format!("{} never reaches a node ending in {}", starting_node, end_suffix));
+        }
+        if path_idx == network.path.len() {
+            path_idx = 0;
+        }
+
+        cur = step(network, &cur, path_idx);
+        frames::status(|| format!("from {}, step {}: {} to {}", starting_node, steps,
+                                  network.path.as_bytes()[path_idx] as char, cur));
+
+        // if we see the same node at the same place in the path, we've found
+        // a cycle, and can stop, since the first time we see it will always
+        // be the same cycle with this input data.
+        if cur.ends_with(end_suffix) {
+            return Ok(steps);
+        }
+
+        path_idx += 1;
+        steps += 1;
+    }
+}
+
+// Where the walks start and end, which aoc.toml can change: part 1 goes
+// from start to end, and part 2 from every node ending in start_suffix to
+// one ending in end_suffix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub start : String,
+    pub end : String,
+    pub start_suffix : String,
+    pub end_suffix : String,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            start: "AAA".to_string(),
+            end: "ZZZ".to_string(),
+            start_suffix: "A".to_string(),
+            end_suffix: "Z".to_string(),
+        }
+    }
+}
+
+impl Params {
+    pub fn from_config(params : &config::Params) -> Result<Params, String> {
+        params.only(&["start", "end", "start_suffix", "end_suffix"])?;
+        let default = Params::default();
+
+        Ok(Params {
+            start: params.string("start", &default.start)?,
+            end: params.string("end", &default.end)?,
+            start_suffix: params.string("start_suffix", &default.start_suffix)?,
+            end_suffix: params.string("end_suffix", &default.end_suffix)?,
+        })
+    }
+}
+
+// The parts fail when the start isn't a node, since it can be set in
+// aoc.toml, or when the walk never gets to the end.
+pub fn part1(network : &Input) -> Result<Answer, String> {
+    part1_with(network, &Params::default())
+}
+
+pub fn part1_with(network : &Input, params : &Params) -> Result<Answer, String> {
+    if !network.nodes.contains_key(&params.start) {
+        return Err(format!("there's no node {} to start from", params.start));
+    }
+    steps_to_zzz(&params.start, &params.end, network)
+}
+
+pub fn part2(network : &Input) -> Result<Answer, String> {
+    part2_with(network, &Params::default())
+}
+
+pub fn part2_with(network : &Input, params : &Params) -> Result<Answer, String> {
+    // for each of the starting nodes in part 2 (every node ending in A),
+    // find the path to a xxZ node, record the number of steps. The least
+    // common multiple of all of these will be the answer.
+    let starts : Vec<&String> = network.nodes.keys().
+        filter(|label| label.ends_with(params.start_suffix.as_str())).
+        collect();
+    if starts.is_empty() {
+        return Err(format!("there are no nodes ending in {} to start from", params.start_suffix));
+    }
+
+    let mut answer = 1;
+    for start in starts {
+        let steps = steps_to_xxz(start, &params.end_suffix, network)?;
+        info!("{} reaches a xxZ node after {} steps", start, steps);
+        answer = lcm(answer, steps);
+    }
+
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+
+    #[test]
+    fn test_case() {
+        let input1 = Source::in_dir(env!("CARGO_MANIFEST_DIR"), "input_test.txt").read().
+            expect("Something went wrong reading the file");
+        let input2 = Source::in_dir(env!("CARGO_MANIFEST_DIR"), "input_test2.txt").read().
+            expect("Something went wrong reading the file");
+
+        assert_eq!(super::part1(&super::parse(&input1).unwrap()), Ok(2));
+        assert_eq!(super::part1(&super::parse(&input2).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn test_case_part2() {
+        let input3 = Source::in_dir(env!("CARGO_MANIFEST_DIR"), "input_test3.txt").read().
+            expect("Something went wrong reading the file");
+
+        assert_eq!(super::part2(&super::parse(&input3).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn test_no_way_there() {
+        let network = super::parse("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, CCC)\nCCC = (CCC, CCC)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(super::part1(&network), Err("AAA never reaches ZZZ".to_string()));
+        assert_eq!(super::part2(&network), Err("AAA never reaches a node ending in Z".to_string()));
+
+        let params = super::Params { start: "QQQ".to_string(), start_suffix: "Q".to_string(), ..super::Params::default() };
+        assert_eq!(super::part1_with(&network, &params), Err("there's no node QQQ to start from".to_string()));
+        assert!(super::part2_with(&network, &params).is_err());
+    }
+}
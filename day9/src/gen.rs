@@ -0,0 +1,37 @@
+// Random but valid inputs for property tests.
+use proptest::prelude::*;
+
+// A history sampled from a polynomial at x = 0, 1, ..., along with the
+// values the polynomial takes just after and just before the samples.
+#[derive(Debug, Clone)]
+pub struct History {
+    pub values: Vec<i64>,
+    pub next: i64,
+    pub prev: i64,
+}
+
+fn eval(coeffs: &[i64], x: i64) -> i64 {
+    coeffs.iter().rev().fold(0, |acc, c| acc * x + c)
+}
+
+// A polynomial of degree up to 5 sampled at enough points to pin it down.
+pub fn history() -> impl Strategy<Value = History> {
+    prop::collection::vec(-20i64..=20, 1..=6).prop_flat_map(|coeffs| {
+        (coeffs.len()..=21).prop_map(move |len| History {
+            values: (0..len as i64).map(|x| eval(&coeffs, x)).collect(),
+            next: eval(&coeffs, len as i64),
+            prev: eval(&coeffs, -1),
+        })
+    })
+}
+
+pub fn to_input(histories: &[History]) -> String {
+    histories.iter().
+        map(|h| h.values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" ") + "\n").
+        collect()
+}
+
+// A whole input, with the histories it's made of.
+pub fn input() -> impl Strategy<Value = (String, Vec<History>)> {
+    prop::collection::vec(history(), 1..20).prop_map(|histories| (to_input(&histories), histories))
+}
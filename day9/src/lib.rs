@@ -0,0 +1,102 @@
+use common::{parse, ParseError};
+
+pub const DAY: u32 = 9;
+
+#[cfg(test)]
+mod gen;
+
+pub type Input = Vec<Vec<i64>>;
+pub type Answer = i64;
+
+// given a vector of nums, predict the next value of the history
+// recursively.
+pub fn predict_next(v : &[i64]) -> i64 {
+
+    // check for all zeroes, this is the base case.
+    if v.iter().all(|i| *i == 0) {
+        return 0;
+    }
+
+    let mut next_vec : Vec<i64> = Vec::new();
+
+    // determine next vector by subtracting each element from the last
+    for i in 0..v.len()-1 {
+        next_vec.push(v[i+1] - v[i]);
+    }
+
+    v.last().unwrap() + predict_next(&next_vec)
+}
+
+pub fn predict_prev(v : &[i64]) -> i64 {
+
+    // check for all zeroes, this is the base case.
+    if v.iter().all(|i| *i == 0) {
+        return 0;
+    }
+
+    let mut next_vec : Vec<i64> = Vec::new();
+
+    // determine next vector by subtracting each element from the last as in part 1
+    for i in 0..v.len()-1 {
+        next_vec.push(v[i+1] - v[i]);
+    }
+
+    v.first().unwrap() - predict_prev(&next_vec)
+}
+
+// One history per line, blank lines are ignored.
+pub fn parse(input : &str) -> Result<Input, ParseError> {
+    parse::lines(DAY, input).
+        filter(|line| !line.is_blank()).
+        map(|line| line.numbers(line.text)).
+        collect()
+}
+
+pub fn part1(histories : &Input) -> Answer {
+    histories.iter().map(|history| predict_next(history)).sum()
+}
+
+pub fn part2(histories : &Input) -> Answer {
+    histories.iter().map(|history| predict_prev(history)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::input::Source;
+    use proptest::prelude::*;
+
+    use crate::gen;
+
+    #[test]
+    fn test_part1() {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), "input_test.txt").read().
+            expect("Something went wrong reading the file");
+
+        assert_eq!(super::part1(&super::parse(&input).unwrap()), 114);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = Source::in_dir(env!("CARGO_MANIFEST_DIR"), "input_test.txt").read().
+            expect("Something went wrong reading the file");
+
+        assert_eq!(super::part2(&super::parse(&input).unwrap()), 2);
+    }
+
+    proptest! {
+        // the prediction is the generating polynomial's next (and previous)
+        // value
+        #[test]
+        fn test_predict_polynomial(history in gen::history()) {
+            prop_assert_eq!(super::predict_next(&history.values), history.next);
+            prop_assert_eq!(super::predict_prev(&history.values), history.prev);
+        }
+
+        #[test]
+        fn test_generated_input((input, histories) in gen::input()) {
+            let parsed = super::parse(&input).unwrap();
+            prop_assert_eq!(super::part1(&parsed), histories.iter().map(|h| h.next).sum::<i64>());
+            prop_assert_eq!(super::part2(&parsed), histories.iter().map(|h| h.prev).sum::<i64>());
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// (row, col) offsets to the 4 orthogonal neighbours: up, down, left, right
+const DELTAS_4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+// (row, col) offsets to all 8 surrounding neighbours, row by row
+const DELTAS_8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    ( 0, -1),          ( 0, 1),
+    ( 1, -1), ( 1, 0), ( 1, 1),
+];
+
+// A 2-d matrix stored row-major, indexed by (row, col).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    num_rows: usize,
+    num_cols: usize,
+    cells: Vec<T>,
+}
+
+// Text that can't be loaded into a grid because a line is longer or shorter
+// than the first one. Lines and columns are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedLine {
+    pub line: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RaggedLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {} has {} columns, expected {}", self.line, self.found, self.expected)
+    }
+}
+
+impl std::error::Error for RaggedLine {}
+
+impl<T> Grid<T> {
+    pub fn filled_with(value: T, num_rows: usize, num_cols: usize) -> Self where T: Clone {
+        Grid { num_rows, num_cols, cells: vec![value; num_rows * num_cols] }
+    }
+
+    // Build a grid by calling f with each (row, col) position.
+    pub fn from_fn(num_rows: usize, num_cols: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let mut cells = Vec::with_capacity(num_rows * num_cols);
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                cells.push(f((row, col)));
+            }
+        }
+
+        Grid { num_rows, num_cols, cells }
+    }
+
+    // Load a grid from text, one row per line, converting each character with
+    // f. The dimensions come from the text itself: blank lines at the end
+    // (i.e. a trailing newline) are ignored and every other line must be the
+    // same length as the first.
+    pub fn parse_with(text: &str, mut f: impl FnMut(char) -> T) -> Result<Self, RaggedLine> {
+        let lines: Vec<&str> = text.trim_end_matches(['\n', '\r']).lines().collect();
+        let num_rows = lines.len();
+        let num_cols = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(num_rows * num_cols);
+
+        for (i, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != num_cols {
+                return Err(RaggedLine { line: i + 1, expected: num_cols, found });
+            }
+            cells.extend(line.chars().map(&mut f));
+        }
+
+        Ok(Grid { num_rows, num_cols, cells })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.num_rows && pos.1 < self.num_cols
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.0 * self.num_cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.num_cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    // Move from pos by delta, if that stays inside the grid.
+    pub fn offset(&self, pos: (usize, usize), delta: (isize, isize)) -> Option<(usize, usize)> {
+        let row = pos.0.checked_add_signed(delta.0)?;
+        let col = pos.1.checked_add_signed(delta.1)?;
+
+        if self.contains((row, col)) { Some((row, col)) } else { None }
+    }
+
+    // Positions of the up, down, left and right neighbours that are inside the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS_4.iter().filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    // Positions of all 8 surrounding neighbours (including diagonals) that are
+    // inside the grid.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS_8.iter().filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.num_cols..(row + 1) * self.num_cols]
+    }
+
+    pub fn rows_iter(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.num_rows).map(move |row| self.row(row))
+    }
+
+    pub fn column_iter(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.num_cols, "column {} out of range", col);
+        self.cells.iter().skip(col).step_by(self.num_cols)
+    }
+
+    pub fn columns_iter(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.num_cols).map(move |col| self.column_iter(col))
+    }
+
+    // Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let num_cols = self.num_cols;
+        (0..self.num_rows * num_cols).map(move |i| (i / num_cols, i % num_cols))
+    }
+
+    // Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { num_rows: self.num_rows, num_cols: self.num_cols, cells: self.cells.iter().map(f).collect() }
+    }
+
+    // Swap rows and columns.
+    pub fn transpose(&self) -> Grid<T> where T: Clone {
+        Grid::from_fn(self.num_cols, self.num_rows, |(row, col)| self[(col, row)].clone())
+    }
+
+    // Rotate a quarter turn clockwise: the first column becomes the first row, reversed.
+    pub fn rotate_cw(&self) -> Grid<T> where T: Clone {
+        Grid::from_fn(self.num_cols, self.num_rows, |(row, col)| self[(self.num_rows - 1 - col, row)].clone())
+    }
+
+    // Rotate a quarter turn counter-clockwise: the last column becomes the first row.
+    pub fn rotate_ccw(&self) -> Grid<T> where T: Clone {
+        Grid::from_fn(self.num_cols, self.num_rows, |(row, col)| self[(col, self.num_cols - 1 - row)].clone())
+    }
+}
+
+impl Grid<char> {
+    // Load a grid of characters from text, see parse_with().
+    pub fn parse(text: &str) -> Result<Self, RaggedLine> {
+        Grid::parse_with(text, |c| c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("position {:?} out of range for {}x{} grid", pos, self.num_rows, self.num_cols),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (num_rows, num_cols) = (self.num_rows, self.num_cols);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("position {:?} out of range for {}x{} grid", pos, num_rows, num_cols),
+        }
+    }
+}
+
+// Print one row per line, with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows_iter() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("ab\ncd\nef\n").unwrap();
+        assert_eq!((grid.num_rows(), grid.num_columns()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+
+        assert_eq!(Grid::parse("ab\nc"), Err(RaggedLine { line: 2, expected: 2, found: 1 }));
+        assert_eq!(Grid::parse("").unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::filled_with(0, 3, 3);
+        assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((0, 2)).collect::<Vec<_>>(), vec![(0, 1), (1, 1), (1, 2)]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(grid.rows_iter().map(|r| r.iter().collect::<String>()).collect::<Vec<_>>(), vec!["abc", "def"]);
+        assert_eq!(grid.columns_iter().map(|c| c.collect::<String>()).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_transpose_rotate() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}